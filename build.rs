@@ -2,18 +2,44 @@ extern crate cpp_build;
 
 extern crate pkg_config;
 
+use std::env;
+use std::path::{Path, PathBuf};
+
 fn main() {
-    let eigen = pkg_config::probe_library("eigen3").expect("Library eigen3 not found");
+    let emscripten = env::var("TARGET").unwrap().contains("emscripten");
+    let no_nlopt = env::var_os("CARGO_FEATURE_NO_NLOPT").is_some();
+
+    // pkg-config only knows about the host, so the Eigen headers have to be given explicitly
+    // when cross compiling for the browser
+    let eigen_include_paths = if emscripten {
+        let dir = env::var("EIGEN3_INCLUDE_DIR")
+            .expect("EIGEN3_INCLUDE_DIR must be set when building for emscripten");
+        vec![PathBuf::from(dir)]
+    } else {
+        pkg_config::probe_library("eigen3").expect("Library eigen3 not found").include_paths
+    };
 
     let mut config = cpp_build::Config::new();
     config.include("sequential-line-search/include/");
-    for path in &eigen.include_paths {
+    for path in &eigen_include_paths {
         config.include(path);
     }
+    if no_nlopt {
+        config.define("SLS_NO_NLOPT", None);
+    }
 
-    let dst = cmake::Config::new("sequential-line-search")
-        .define("SEQUENTIAL_LINE_SEARCH_BUILD_COMMAND_DEMOS", "OFF")
-        .build();
+    let mut cmake_config = cmake::Config::new("sequential-line-search");
+    cmake_config.define("SEQUENTIAL_LINE_SEARCH_BUILD_COMMAND_DEMOS", "OFF");
+    if emscripten {
+        let root = env::var("EMSCRIPTEN")
+            .expect("EMSCRIPTEN must point to the emscripten installation");
+        cmake_config.define(
+            "CMAKE_TOOLCHAIN_FILE",
+            Path::new(&root).join("cmake/Modules/Platform/Emscripten.cmake"),
+        );
+        cmake_config.define("EIGEN3_INCLUDE_DIR", &eigen_include_paths[0]);
+    }
+    let dst = cmake_config.build();
     //config.object(dst.join("lib/libSequentialLineSearch.a"));
 
     println!(
@@ -21,7 +47,15 @@ fn main() {
         dst.join("lib").display()
     );
     println!("cargo:rustc-link-lib=static=SequentialLineSearch");
-    println!("cargo:rustc-link-lib=dylib=nlopt");
+    // The C++ library fits the regression with nlopt, so it is needed even with no-nlopt
+    if emscripten {
+        let dir = env::var("NLOPT_LIB_DIR")
+            .expect("NLOPT_LIB_DIR must point to an emscripten build of nlopt");
+        println!("cargo:rustc-link-search=native={}", dir);
+        println!("cargo:rustc-link-lib=static=nlopt");
+    } else {
+        println!("cargo:rustc-link-lib=dylib=nlopt");
+    }
 
     config.build("src/lib.rs");
 }
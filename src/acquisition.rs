@@ -0,0 +1,57 @@
+use std::os::raw::c_void;
+
+use crate::SLSFramework;
+
+type AcquisitionFn = Box<dyn Fn(f64, f64) -> f64 + Send + Sync>;
+
+extern "C" fn call_acquisition(f: *mut c_void, mean: f64, std: f64) -> f64 {
+    let f = unsafe { &*(f as *const AcquisitionFn) };
+    f(mean, std)
+}
+
+extern "C" fn drop_acquisition(f: *mut c_void) {
+    drop(unsafe { Box::from_raw(f as *mut AcquisitionFn) });
+}
+
+impl SLSFramework {
+    /// Replace the expected improvement by a custom acquisition function.
+    ///
+    /// `f` receives the posterior mean and standard deviation of the latent score at a candidate
+    /// point and returns its score, the next slider leads from the maximum of the posterior mean
+    /// to the candidate with the highest score. Both are found without nlopt, with the search of
+    /// [deterministic acquisition](SLSFramework::set_deterministic_acquisition): Halton points in
+    /// the unit cube and the sampled points, refined by a compass search. That search evaluates
+    /// `f` a few thousand times per step and is much less thorough than nlopt, so expect slower
+    /// steps and somewhat worse sliders in high dimensions.
+    ///
+    /// [acquisition_value](SLSFramework::acquisition_value) still reports the expected
+    /// improvement. A panic in `f` aborts the process.
+    pub fn set_custom_acquisition<F>(&mut self, f: F)
+    where
+        F: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    {
+        let f: Box<AcquisitionFn> = Box::new(Box::new(f));
+        let f = Box::into_raw(f) as *mut c_void;
+        let call: extern "C" fn(*mut c_void, f64, f64) -> f64 = call_acquisition;
+        let release: extern "C" fn(*mut c_void) = drop_acquisition;
+        unsafe {
+            cpp!([self as "SLSFramework*", f as "void*", call as "AcquisitionCallback", release as "ClosureDrop"] {
+                // Shared between clones of the framework, dropped with the last one
+                const std::shared_ptr<void> closure(f, release);
+                self->custom_acquisition = [closure, call](double mean, double sd) {
+                    return call(closure.get(), mean, sd);
+                };
+            });
+        }
+    }
+
+    /// Go back to maximizing the expected improvement after
+    /// [set_custom_acquisition](SLSFramework::set_custom_acquisition).
+    pub fn clear_custom_acquisition(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->custom_acquisition = nullptr;
+            });
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Synthetic benchmarks of the algorithm
+//!
+//! Each benchmark runs [SLSFramework] against a rater that always picks the position on the
+//! slider with the lowest value of a known test function, and records how close the inferred
+//! best gets to the known minimum.
+
+use std::f64::consts::{E, PI};
+
+use crate::SLSFramework;
+
+/// Outcome of one benchmark run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    /// Name of the test function
+    pub name: &'static str,
+    /// Distance from the best position to date to the minimum, after each iteration
+    ///
+    /// This is the best distance so far, so it never increases.
+    pub best_distance: Vec<f64>,
+}
+
+struct TestFunction {
+    name: &'static str,
+    f: fn(&[f64]) -> f64,
+    // The minimum has this value in every coordinate
+    minimum: f64,
+}
+
+// All test functions are mapped onto the unit cube, with their minimum away from its center
+
+fn sphere(x: &[f64]) -> f64 {
+    x.iter().map(|xi| (xi - 0.3) * (xi - 0.3)).sum()
+}
+
+// Defined on [-2, 2], minimum at z = 1
+fn rosenbrock(x: &[f64]) -> f64 {
+    let z: Vec<f64> = x.iter().map(|xi| 4. * xi - 2.).collect();
+    if z.len() == 1 {
+        return (1. - z[0]) * (1. - z[0]);
+    }
+    z.windows(2)
+        .map(|w| 100. * (w[1] - w[0] * w[0]).powi(2) + (1. - w[0]).powi(2))
+        .sum()
+}
+
+// Defined on [-4, 6], minimum at z = 0
+fn ackley(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let z: Vec<f64> = x.iter().map(|xi| 10. * (xi - 0.4)).collect();
+    let squares = z.iter().map(|zi| zi * zi).sum::<f64>() / n;
+    let cosines = z.iter().map(|zi| (2. * PI * zi).cos()).sum::<f64>() / n;
+    -20. * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20. + E
+}
+
+const SUITE: [TestFunction; 3] = [
+    TestFunction { name: "sphere", f: sphere, minimum: 0.3 },
+    TestFunction { name: "rosenbrock", f: rosenbrock, minimum: 0.75 },
+    TestFunction { name: "ackley", f: ackley, minimum: 0.4 },
+];
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(ai, bi)| (ai-bi)*(ai-bi)).sum::<f64>().sqrt()
+}
+
+fn run(test: &TestFunction, dim: usize, iterations: usize, seed: u64) -> BenchmarkResult {
+    let positions: Vec<f64> = (0..=100).map(|i| i as f64 / 100.).collect();
+    let minimum = vec![test.minimum; dim];
+
+    let mut sls = SLSFramework::new_with_rng(dim, seed);
+    let mut best_distance = Vec::with_capacity(iterations);
+    let mut best = f64::INFINITY;
+    for _ in 0..iterations {
+        let (pos, _) = sls
+            .get_parameters_from_slider_many(&positions)
+            .iter()
+            .map(|x| (test.f)(x))
+            .enumerate()
+            .fold((0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a });
+        sls.proceed_optimization(positions[pos]);
+
+        best = best.min(distance(&sls.get_x_max(), &minimum));
+        best_distance.push(best);
+    }
+
+    BenchmarkResult { name: test.name, best_distance }
+}
+
+/// Run every standard test function
+///
+/// The suite minimizes a sphere, the Rosenbrock function and the Ackley function, each mapped
+/// onto the unit cube, for `iterations` steps in `dim` dimensions. Each run uses its own random
+/// number generator seeded with `seed`, see [new_with_rng](SLSFramework::new_with_rng).
+pub fn standard_suite(dim: usize, iterations: usize, seed: u64) -> Vec<BenchmarkResult> {
+    SUITE.iter().map(|test| run(test, dim, iterations, seed)).collect()
+}
@@ -0,0 +1,57 @@
+use std::os::raw::c_void;
+use std::slice;
+use std::sync::Mutex;
+
+use crate::SLSFramework;
+
+// Clones of a framework share the callback, the mutex keeps calls from overlapping
+type NewBestFn = Mutex<Box<dyn FnMut(&[f64], f64) + Send>>;
+
+extern "C" fn call_new_best(cb: *mut c_void, x: *const f64, dim: usize, y: f64) {
+    let cb = unsafe { &*(cb as *const NewBestFn) };
+    let x = unsafe { slice::from_raw_parts(x, dim) };
+    (cb.lock().unwrap())(x, y)
+}
+
+extern "C" fn drop_new_best(cb: *mut c_void) {
+    drop(unsafe { Box::from_raw(cb as *mut NewBestFn) });
+}
+
+impl SLSFramework {
+    /// Call `cb` whenever a step finds a new best point.
+    ///
+    /// After every [proceed_optimization](SLSFramework::proceed_optimization) (or any other
+    /// step) whose best latent score is strictly greater than the one before the step, `cb`
+    /// receives the new best point and its score, as returned by
+    /// [get_x_max](SLSFramework::get_x_max). The first step always counts as an improvement.
+    /// There is no tolerance, but every step re-estimates all scores, so the best point can
+    /// stay the same while its score rises. Steps that lower the best score are not reported.
+    ///
+    /// Clones of the framework share `cb`. A panic in `cb` aborts the process.
+    pub fn set_on_new_best<F>(&mut self, cb: F)
+    where
+        F: FnMut(&[f64], f64) + Send + 'static,
+    {
+        let cb: Box<NewBestFn> = Box::new(Mutex::new(Box::new(cb)));
+        let cb = Box::into_raw(cb) as *mut c_void;
+        let call: extern "C" fn(*mut c_void, *const f64, usize, f64) = call_new_best;
+        let release: extern "C" fn(*mut c_void) = drop_new_best;
+        unsafe {
+            cpp!([self as "SLSFramework*", cb as "void*", call as "NewBestCallback", release as "ClosureDrop"] {
+                const std::shared_ptr<void> closure(cb, release);
+                self->on_new_best = [closure, call](const VectorXd& x, double y) {
+                    call(closure.get(), x.data(), x.size(), y);
+                };
+            });
+        }
+    }
+
+    /// Stop calling the callback of [set_on_new_best](SLSFramework::set_on_new_best).
+    pub fn clear_on_new_best(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->on_new_best = nullptr;
+            });
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{SLSError, SLSFramework};
+
+/// Sequential line search for two objectives at once
+///
+/// Holds one [SLSFramework] per objective, e.g. how pretty and how fast a configuration looks.
+/// Both share the same slider, and every answer updates both models: either one position for
+/// both objectives, or one per objective.
+///
+/// Once both objectives have data, sliders lead from the best point of the first objective to
+/// the maximum of `w * EI_0(x) + (1 - w) * EI_1(x)`, where `EI_i` is the expected improvement
+/// of objective `i` (see [SLSFramework::acquisition_value]) and `w` the
+/// [weight](DualObjectiveSLS::set_weight). The expected improvements are not normalized, so an
+/// objective whose scores vary more pulls harder. The combined acquisition function is
+/// maximized once per step with the search of
+/// [deterministic acquisition](SLSFramework::set_deterministic_acquisition), and takes the place
+/// of the [next candidate](SLSFramework::set_next_candidate) of the first objective. Everything
+/// else about the slider, like the anchor, range decay and jitter, follows the settings of the
+/// first objective. The initial slider is that of the first objective, and while either
+/// objective is in [warm-up](SLSFramework::set_warmup_steps), both use the random slider it
+/// generates, the first objective taking precedence.
+#[derive(Clone)]
+pub struct DualObjectiveSLS {
+    objectives: (SLSFramework, SLSFramework),
+    weight: f64,
+}
+
+impl DualObjectiveSLS {
+    /// Initializes both objectives for a parameter space of `dim` dimensions
+    ///
+    /// The weight starts at `0.5`.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [SLSFramework::new].
+    pub fn new(dim: usize) -> Self {
+        match DualObjectiveSLS::try_new(dim) {
+            Ok(dual) => dual,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Initializes both objectives, failing like [SLSFramework::try_new].
+    ///
+    /// See [new](DualObjectiveSLS::new).
+    pub fn try_new(dim: usize) -> Result<Self, SLSError> {
+        let mut dual = DualObjectiveSLS {
+            objectives: (SLSFramework::try_new(dim)?, SLSFramework::try_new(dim)?),
+            weight: 0.5,
+        };
+        dual.share_slider();
+        Ok(dual)
+    }
+
+    /// Set how much the first objective counts when choosing sliders
+    ///
+    /// `w` (`0 <= w <= 1`) weights the expected improvement of the first objective, `1 - w`
+    /// that of the second one. Takes effect with the next slider.
+    pub fn set_weight(&mut self, w: f64) {
+        assert!((0. ..=1.).contains(&w), "weight must be in [0, 1]");
+        self.weight = w;
+    }
+
+    /// Get the frameworks of both objectives
+    ///
+    /// For predictions and all other read access. Their sliders are always the same.
+    pub fn objectives(&self) -> (&SLSFramework, &SLSFramework) {
+        (&self.objectives.0, &self.objectives.1)
+    }
+
+    /// Get positions along the shared slider
+    ///
+    /// See [SLSFramework::get_parameters_from_slider].
+    pub fn get_parameters_from_slider(&self, pos: f64) -> Vec<f64> {
+        self.objectives.0.get_parameters_from_slider(pos)
+    }
+
+    /// Take one step with the same answer for both objectives
+    ///
+    /// Returns the parameters at `pos`. See [SLSFramework::proceed_optimization].
+    pub fn proceed_optimization(&mut self, pos: f64) -> Vec<f64> {
+        self.proceed_optimization_each(pos, pos).0
+    }
+
+    /// Take one step with a separate answer for each objective
+    ///
+    /// `pos_0` is the best position on the shared slider for the first objective, `pos_1` for
+    /// the second one. Returns the parameters at both positions.
+    pub fn proceed_optimization_each(&mut self, pos_0: f64, pos_1: f64) -> (Vec<f64>, Vec<f64>) {
+        let x_0 = self.objectives.0.record_optimization(pos_0);
+        let x_1 = self.objectives.1.record_optimization(pos_1);
+        self.share_slider();
+        (x_0, x_1)
+    }
+
+    fn share_slider(&mut self) {
+        let a = &mut self.objectives.0;
+        let b = &mut self.objectives.1;
+        let w = self.weight;
+        unsafe {
+            cpp!([a as "SLSFramework*", b as "SLSFramework*", w as "double"] {
+                // Before the first step, the initial slider of a is current
+                if (a->data.X.cols() > 0)
+                {
+                    const bool a_warming_up = a->num_iterations < a->warmup_steps;
+                    if (!a_warming_up && b->num_iterations < b->warmup_steps)
+                    {
+                        b->finishStep();
+                        a->replaceSlider(b->slider);
+                        a->slider_acquisition_point = b->slider_acquisition_point;
+                        a->slider_nudged = b->slider_nudged;
+                        a->acquisition_ns = b->acquisition_ns;
+                    }
+                    else
+                    {
+                        // The combined maximum takes the place of the acquisition of a, everything
+                        // else about the slider is up to the settings of a
+                        int64_t joint_ns = 0;
+                        if (!a_warming_up && a->next_candidate.size() == 0)
+                        {
+                            const auto start = std::chrono::steady_clock::now();
+                            if (a->has_schedule) a->acquisition_param = a->scheduledAcquisitionParam();
+                            if (b->has_schedule) b->acquisition_param = b->scheduledAcquisitionParam();
+                            a->next_candidate = a->maximizeDeterministically([&](const VectorXd& x)
+                            {
+                                return w * a->acquisitionValue(x) + (1.0 - w) * b->acquisitionValue(x);
+                            });
+                            joint_ns = std::chrono::duration_cast<std::chrono::nanoseconds>(
+                                std::chrono::steady_clock::now() - start).count();
+                        }
+                        a->finishStep();
+                        a->acquisition_ns += joint_ns;
+                    }
+                }
+                b->replaceSlider(a->slider);
+                b->slider_acquisition_point = a->slider_acquisition_point;
+                b->slider_nudged = a->slider_nudged;
+                b->acquisition_ns = a->acquisition_ns;
+                a->checkHealth();
+                b->checkHealth();
+            });
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by the fallible methods of [SLSFramework](crate::SLSFramework)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SLSError {
+    /// A vector did not have the number of dimensions of the parameter space
+    DimensionMismatch { expected: usize, found: usize },
+    /// An index referred to a data point that does not exist
+    IndexOutOfRange { index: usize, len: usize },
+    /// The comparison at this index rejected no point, or its own preferred point
+    InvalidComparison { index: usize },
+    /// A step index referred to a step that was not taken
+    StepOutOfRange { index: usize, len: usize },
+    /// A position was too far outside of the slider
+    PositionOutOfRange { pos: f64 },
+    /// A categorical group had less than two dimensions, or dimensions that are out of range or
+    /// already in a group
+    InvalidGroup,
+    /// There is no current slider
+    NoSlider,
+    /// Both ends of a slider were the same point
+    IdenticalEndpoints,
+    /// The parameter space needs at least one dimension
+    ZeroDimensions,
+    /// The C++ library threw an exception with this message
+    Cpp(String),
+    /// A comparison was submitted for a slider that is no longer current
+    StaleComparison,
+    /// An [OptionalSLS](crate::OptionalSLS) was used before being initialized
+    Uninitialized,
+}
+
+impl fmt::Display for SLSError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SLSError::DimensionMismatch { expected, found } => {
+                write!(f, "expected a vector of dimension {}, found {}", expected, found)
+            }
+            SLSError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} is out of range for {} data points", index, len)
+            }
+            SLSError::InvalidComparison { index } => {
+                write!(f, "comparison {} must reject a point other than its preferred one", index)
+            }
+            SLSError::StepOutOfRange { index, len } => {
+                write!(f, "step {} is out of range for {} steps", index, len)
+            }
+            SLSError::PositionOutOfRange { pos } => {
+                write!(f, "position {} is out of range for the slider", pos)
+            }
+            SLSError::InvalidGroup => {
+                write!(f, "a categorical group needs two or more unused dimensions in range")
+            }
+            SLSError::NoSlider => write!(f, "there is no current slider"),
+            SLSError::IdenticalEndpoints => write!(f, "both ends of the slider are the same point"),
+            SLSError::ZeroDimensions => {
+                write!(f, "the parameter space needs at least one dimension")
+            }
+            SLSError::Cpp(message) => write!(f, "C++ exception: {}", message),
+            SLSError::StaleComparison => {
+                write!(f, "the comparison was made on a slider that is no longer current")
+            }
+            SLSError::Uninitialized => write!(f, "the framework has not been initialized"),
+        }
+    }
+}
+
+impl Error for SLSError {}
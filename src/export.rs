@@ -0,0 +1,69 @@
+use crate::{as_rust_vec, SLSFramework};
+
+/// Covariance function of a [PortableModel]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Kernel {
+    /// `k(x, x') = signal_variance * exp(-|x - x'|^2 / (2 * length_scale^2))`
+    SquaredExponential,
+}
+
+/// The fitted regression as plain data, to make predictions without this crate
+///
+/// Obtained from [SLSFramework::export_model]. The [kernel](Kernel) is always the squared
+/// exponential kernel
+///
+/// `k(x, x') = signal_variance * exp(-|x - x'|^2 / (2 * length_scale^2))`
+///
+/// With `K` the kernel matrix of `points`, `C = K + noise * I` and `k(x)` the vector of kernel
+/// values between `x` and each of `points`, the model predicts
+///
+/// - mean: `k(x)^T C^-1 latent`
+/// - variance: `signal_variance + noise - k(x)^T C^-1 k(x)`
+///
+/// which is what [predict](SLSFramework::predict) returns. With the `serde` feature, the struct
+/// can be serialized, otherwise how it is stored is up to the caller.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortableModel {
+    /// The covariance function
+    pub kernel: Kernel,
+    /// The sampled points
+    pub points: Vec<Vec<f64>>,
+    /// The fitted latent score of each point
+    pub latent: Vec<f64>,
+    /// Signal variance of the kernel (`a` in the C++ library)
+    pub signal_variance: f64,
+    /// Length scale of the kernel (`r` in the C++ library)
+    pub length_scale: f64,
+    /// Variance added to the diagonal of the kernel matrix (`b` in the C++ library)
+    pub noise: f64,
+}
+
+impl SLSFramework {
+    /// Export the regression, see [PortableModel]
+    pub fn export_model(&self) -> PortableModel {
+        let mut hyperparameters = [0.0f64; 3];
+        let ptr = hyperparameters.as_mut_ptr();
+        let latent = unsafe {
+            cpp!([self as "SLSFramework*", ptr as "double*"] {
+                ptr[0] = self->getRegressor().a;
+                ptr[1] = self->getRegressor().r;
+                ptr[2] = self->getRegressor().b;
+            });
+            let y = cpp!([self as "SLSFramework*"] -> *const u8 as "const VectorXd *" {
+                return &self->getRegressor().y;
+            });
+            as_rust_vec(y)
+        };
+
+        PortableModel {
+            kernel: Kernel::SquaredExponential,
+            points: self.get_all_points(),
+            latent,
+            signal_variance: hyperparameters[0],
+            length_scale: hyperparameters[1],
+            noise: hyperparameters[2],
+        }
+    }
+}
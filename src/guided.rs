@@ -0,0 +1,41 @@
+use crate::{as_rust_vec, SLSError, SLSFramework};
+
+impl SLSFramework {
+    /// Set the optimum of a simulated rater for [auto_step](SLSFramework::auto_step)
+    ///
+    /// Useful for demos and tutorials where the correct answer is known. Fails if `target` has
+    /// the wrong dimension.
+    pub fn set_reference_target(&mut self, target: &[f64]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        self.check_dimension(target)?;
+        let target = target.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", target as "const double*", dim as "size_t"] {
+                self->reference_target = Map<const VectorXd>(target, dim);
+            });
+        }
+        Ok(())
+    }
+
+    /// Take a step as an ideal rater would
+    ///
+    /// Chooses the [position closest](crate::Slider::closest_position) to the
+    /// [reference target](SLSFramework::set_reference_target) on the current slider and passes it
+    /// to [proceed_optimization](SLSFramework::proceed_optimization), whose result is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no reference target has been set.
+    pub fn auto_step(&mut self) -> Vec<f64> {
+        let target = unsafe {
+            let target = cpp!([self as "SLSFramework*"] -> *const u8 as "const VectorXd *" {
+                return &self->reference_target;
+            });
+            as_rust_vec(target)
+        };
+        assert!(!target.is_empty(), "auto_step called without a reference target");
+
+        let pos = self.current_slider().closest_position(&target);
+        self.proceed_optimization(pos)
+    }
+}
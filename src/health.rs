@@ -0,0 +1,51 @@
+use crate::SLSFramework;
+
+/// Problems detected during [SLSFramework::proceed_optimization]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthWarning {
+    /// MAP estimation produced non-finite or non-positive hyperparameters.
+    ///
+    /// The model is likely useless, disable
+    /// [MAP estimation](SLSFramework::set_use_map_hyperparameters) or collect more data first.
+    HyperoptFailed,
+    /// The condition number of the kernel matrix exceeds the
+    /// [threshold](SLSFramework::set_condition_threshold).
+    ///
+    /// This happens with many nearly identical points, remove some of them with
+    /// [remove_point](SLSFramework::remove_point).
+    IllConditioned,
+    /// Both ends of the new slider coincided and one was nudged in a random direction.
+    ///
+    /// The model is confident about the optimum, which is a good time to stop.
+    ZeroLengthSlider,
+}
+
+impl SLSFramework {
+    /// Get the problem detected in the most recent step, if any
+    ///
+    /// If several problems occurred, the one listed first in [HealthWarning] is reported.
+    pub fn health_warning(&self) -> Option<HealthWarning> {
+        let code = unsafe {
+            cpp!([self as "SLSFramework*"] -> i32 as "int" {
+                return self->health_warning;
+            })
+        };
+        match code {
+            1 => Some(HealthWarning::HyperoptFailed),
+            2 => Some(HealthWarning::IllConditioned),
+            3 => Some(HealthWarning::ZeroLengthSlider),
+            _ => None,
+        }
+    }
+
+    /// Set the condition number above which the kernel matrix is considered ill-conditioned
+    ///
+    /// The default is `1e12`.
+    pub fn set_condition_threshold(&mut self, threshold: f64) {
+        unsafe {
+            cpp!([self as "SLSFramework*", threshold as "double"] {
+                self->condition_threshold = threshold;
+            });
+        }
+    }
+}
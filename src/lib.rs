@@ -32,158 +32,2126 @@
 
 #![recursion_limit="512"]
 
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::time::Duration;
+
 #[macro_use]
 extern crate cpp;
 
-cpp! {{
-    #include <iostream>
-    #include <memory>
-    #include <sequential-line-search/sequential-line-search.h>
-    using namespace sequential_line_search;
-    using namespace Eigen;
+mod acquisition;
+pub mod benchmark;
+mod callback;
+mod dual;
+mod error;
+mod export;
+mod guided;
+mod health;
+mod model;
+mod optimize;
+mod optional;
+mod profile;
+mod record;
+mod schedule;
+mod session;
+mod slider;
+mod snapshot;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod timings;
+
+pub use dual::DualObjectiveSLS;
+pub use error::SLSError;
+pub use export::{Kernel, PortableModel};
+pub use health::HealthWarning;
+pub use optimize::SessionResult;
+pub use optional::OptionalSLS;
+pub use profile::Profile;
+pub use record::StepRecord;
+pub use schedule::Schedule;
+pub use session::Comparison;
+pub use slider::{AnchorMode, InitialStrategy, Slider};
+pub use snapshot::StateSnapshot;
+pub use timings::StepTimings;
+
+cpp! {{
+    #include <algorithm>
+    #include <chrono>
+    #include <cmath>
+    #include <cstdint>
+    #include <cstdlib>
+    #include <cstring>
+    #include <functional>
+    #include <iostream>
+    #include <memory>
+    #include <mutex>
+    #include <random>
+    #include <stdexcept>
+    #include <string>
+    #include <Eigen/Dense>
+    #include <sequential-line-search/sequential-line-search.h>
+    using namespace sequential_line_search;
+    using namespace Eigen;
+
+    // Counts the frameworks alive on the current thread, to check that Rust destroys them
+    struct InstanceCounter {
+        static thread_local int64_t live;
+
+        InstanceCounter() { ++live; }
+        InstanceCounter(const InstanceCounter&) { ++live; }
+        ~InstanceCounter() { --live; }
+    };
+    thread_local int64_t InstanceCounter::live = 0;
+
+    // A mutex that every copy of a framework gets its own of
+    struct CopyableMutex {
+        std::mutex mutex;
+
+        CopyableMutex() {}
+        CopyableMutex(const CopyableMutex&) {}
+        CopyableMutex& operator=(const CopyableMutex&) { return *this; }
+    };
+
+    // Drops a boxed Rust closure
+    typedef void (*ClosureDrop)(void*);
+
+    // A Rust closure scoring a candidate from its posterior mean and standard deviation
+    typedef double (*AcquisitionCallback)(void*, double, double);
+
+    // A Rust closure receiving a new best point, its dimension and its score
+    typedef void (*NewBestCallback)(void*, const double*, size_t, double);
+
+    struct SLSFramework {
+        InstanceCounter counter;
+
+        // Rebuilt on demand after finalize(), access it through getRegressor()
+        mutable std::shared_ptr<sequential_line_search::PreferenceRegressor> regressor;
+        // Held while getRegressor() checks for and rebuilds the regression, so that threads
+        // sharing a framework don't rebuild it at the same time
+        mutable CopyableMutex regressor_mutex;
+        std::shared_ptr<sequential_line_search::Slider> slider;
+        // The point generating the current slider was chosen for, before range decay, jitter and
+        // nudging, empty if the slider was set by hand
+        VectorXd slider_acquisition_point;
+
+        sequential_line_search::Data data;
+
+        size_t          dimension;
+        Eigen::VectorXd x_max;
+        double          y_max;
+
+        size_t num_iterations;
+        size_t warmup_steps;
+        double range_decay;
+
+        bool   use_map_hyperparameters;
+        double kernel_a;  // signal variance
+        double kernel_r;  // length scale
+        double kernel_b;  // noise level
+        // Ranges MAP estimates are clamped to
+        double min_a, max_a;
+        double min_r, max_r;
+        double min_b, max_b;
+        size_t acquisition_restarts;
+        double acquisition_timeout;
+
+        double slider_warp;
+        double slider_overshoot;
+        double position_tolerance;
+        // Dimensions forming one-hot encoded categories
+        std::vector<std::vector<size_t>> categorical_groups;
+        bool   deterministic_acquisition;
+        double acquisition_param;
+        // Replaces acquisition_param before each slider if set
+        bool   has_schedule;
+        double schedule_start;
+        double schedule_end;
+        size_t schedule_over;
+        // Replaces the expected improvement if set
+        std::function<double(double, double)> custom_acquisition;
+        // Called by steps that improve y_max
+        std::function<void(const VectorXd&, double)> on_new_best;
+
+        // 0: random pair, 1: antipodal pair, 2: fixed endpoints
+        int      initial_strategy;
+        VectorXd initial_end_0;
+        VectorXd initial_end_1;
+        // Leave the first slider to the user
+        bool skip_initial_slider;
+        // 0: maximum of the posterior mean, 1: most recently chosen point
+        int  anchor_mode;
+        double endpoint_jitter;
+        // Used instead of the acquisition function for the next slider, empty if not set
+        VectorXd next_candidate;
+        // Start of every generated slider, empty if not set
+        VectorXd fixed_anchor;
+
+        // Source of all randomness if seeded, otherwise std::rand is used
+        bool seeded;
+        mutable std::mt19937_64 rng;
+
+        // Optimum of the simulated rater of auto_step, empty if not set
+        VectorXd reference_target;
+
+        // 0: none, 1: hyperparameters failed, 2: ill-conditioned, 3: zero-length slider
+        int    health_warning;
+        double condition_threshold;
+        bool   slider_nudged;
+
+        // Durations of the most recent step
+        int64_t regression_ns;
+        int64_t acquisition_ns;
+
+        uint64_t slider_generation;
+
+        // State before the most recent step, to allow amending it
+        std::shared_ptr<sequential_line_search::Slider> previous_slider;
+        sequential_line_search::Data previous_data;
+        double previous_y_max;
+        std::shared_ptr<sequential_line_search::PreferenceRegressor> previous_regressor;
+
+        // Slider, position and resulting point of the most recent step
+        std::shared_ptr<sequential_line_search::Slider> step_slider;
+        double step_position;
+        // Whether the step chose one of three points, see proceedOptimizationTriple
+        bool step_triple;
+        VectorXd step_point;
+        // slider_acquisition_point of step_slider, and the best point after the step
+        VectorXd step_acquisition_point;
+        VectorXd step_x_max;
+        double step_y_max;
+        // Columns of data.X the step's points ended up in, the chosen one first
+        std::vector<size_t> step_indices;
+
+        // The slider of every step since the last reset, and y_max after it
+        std::vector<std::shared_ptr<sequential_line_search::Slider>> slider_history;
+        std::vector<double> best_history;
+
+        // Absolute ratings, turned into comparisons between each other
+        std::vector<std::pair<VectorXd, double>> absolute_observations;
+
+        SLSFramework(size_t d) :
+            dimension(d),
+            regressor(nullptr), regressor_mutex(), slider(nullptr),
+            slider_acquisition_point(VectorXd::Zero(0)),
+            data(),
+            x_max(VectorXd::Zero(0)),
+            y_max(NAN),
+            num_iterations(0),
+            warmup_steps(1),
+            range_decay(1.0),
+            use_map_hyperparameters(false),
+            kernel_a(0.500),
+            kernel_r(0.500),
+            kernel_b(0.005),
+            min_a(0.0), max_a(INFINITY),
+            min_r(0.0), max_r(INFINITY),
+            min_b(0.0), max_b(INFINITY),
+            acquisition_restarts(1),
+            acquisition_timeout(INFINITY),
+            slider_warp(1.0),
+            slider_overshoot(0.0),
+            position_tolerance(1e-6),
+            categorical_groups(),
+            deterministic_acquisition(false),
+            acquisition_param(0.0),
+            has_schedule(false),
+            schedule_start(0.0),
+            schedule_end(0.0),
+            schedule_over(0),
+            custom_acquisition(),
+            on_new_best(),
+            initial_strategy(0),
+            skip_initial_slider(false),
+            anchor_mode(0),
+            endpoint_jitter(0.0),
+            next_candidate(VectorXd::Zero(0)),
+            fixed_anchor(VectorXd::Zero(0)),
+            reference_target(VectorXd::Zero(0)),
+            seeded(false),
+            rng(),
+            health_warning(0),
+            condition_threshold(1e12),
+            slider_nudged(false),
+            regression_ns(0),
+            acquisition_ns(0),
+            slider_generation(0),
+            previous_slider(nullptr),
+            previous_data(),
+            previous_y_max(NAN),
+            previous_regressor(nullptr),
+            step_slider(nullptr),
+            step_position(NAN),
+            step_triple(false),
+            step_point(VectorXd::Zero(0)),
+            step_acquisition_point(VectorXd::Zero(0)),
+            step_x_max(VectorXd::Zero(0)),
+            step_y_max(NAN),
+            step_indices(),
+            slider_history(),
+            best_history(),
+            absolute_observations()
+        {
+        }
+
+        void seed(uint64_t s)
+        {
+            seeded = true;
+            rng.seed(s);
+        }
+
+        // Uniformly distributed in the unit cube
+        VectorXd randomVector() const
+        {
+            if (!seeded) return utils::generateRandomVector(dimension);
+            std::uniform_real_distribution<double> uniform(0.0, 1.0);
+            VectorXd x(dimension);
+            for (size_t d = 0; d < dimension; ++d) x(d) = uniform(rng);
+            return x;
+        }
+
+        // Seed for generators that only live for one call
+        unsigned int randomSeed() const
+        {
+            return seeded ? static_cast<unsigned int>(rng()) : std::rand();
+        }
+
+        // Separate from the constructor, so that errors can be reported
+        void initialize()
+        {
+            computeRegression();
+            updateSliderEnds();
+        }
+
+        void computeRegression() const
+        {
+            // Without MAP estimation, the kernel_* values are used as hyperparameters
+            regressor = std::make_shared<PreferenceRegressor>(data.X, data.D, VectorXd(), use_map_hyperparameters, kernel_a, kernel_r, kernel_b);
+            if (!use_map_hyperparameters) return;
+
+            // The C++ library can't constrain the estimation, so refit with clamped values
+            const double a = std::min(std::max(regressor->a, min_a), max_a);
+            const double r = std::min(std::max(regressor->r, min_r), max_r);
+            const double b = std::min(std::max(regressor->b, min_b), max_b);
+            if (a != regressor->a || r != regressor->r || b != regressor->b)
+            {
+                regressor = std::make_shared<PreferenceRegressor>(data.X, data.D, VectorXd(), false, a, r, b);
+            }
+        }
+
+        PreferenceRegressor& getRegressor() const
+        {
+            // Only methods taking the framework mutably replace the regression once it exists,
+            // so the reference stays valid after unlocking
+            const std::lock_guard<std::mutex> lock(regressor_mutex.mutex);
+            if (regressor == nullptr) computeRegression();
+            return *regressor;
+        }
+
+        // Release memory that is only needed to continue the optimization
+        void finalize()
+        {
+            regressor = nullptr;
+            previous_slider = nullptr;
+            previous_data = Data();
+            previous_regressor = nullptr;
+        }
+
+        // Expected improvement over the best latent score, with exploration parameter xi
+        double acquisitionValue(const VectorXd& x) const
+        {
+            const double mu = getRegressor().estimate_y(x);
+            const double sigma = getRegressor().estimate_s(x);
+            const double gamma = mu - getRegressor().y.maxCoeff() - acquisition_param;
+            if (sigma <= 0.0) return std::max(gamma, 0.0);
+
+            const double z = gamma / sigma;
+            const double cdf = 0.5 * std::erfc(-z / std::sqrt(2.0));
+            const double pdf = std::exp(-0.5 * z * z) / std::sqrt(2.0 * std::acos(-1.0));
+            return gamma * cdf + sigma * pdf;
+        }
+
+        // Gradient of acquisitionValue with respect to x
+        VectorXd acquisitionGradient(const VectorXd& x) const
+        {
+            const PreferenceRegressor& regressor = getRegressor();
+            const double r = regressor.r;
+
+            // Column i is the gradient of the kernel between x and data point i
+            const VectorXd k = kernelVector(x);
+            MatrixXd dk(x.size(), k.size());
+            for (int i = 0; i < k.size(); ++i) dk.col(i) = -k(i) * (x - regressor.X.col(i)) / (r * r);
+
+            const double mu = regressor.estimate_y(x);
+            const double sigma = regressor.estimate_s(x);
+            const double gamma = mu - regressor.y.maxCoeff() - acquisition_param;
+            const VectorXd d_mu = dk * (regressor.C_inv * regressor.y);
+            if (sigma <= 0.0) return gamma > 0.0 ? d_mu : VectorXd(VectorXd::Zero(x.size()));
+
+            const VectorXd d_sigma = -dk * (regressor.C_inv * k) / sigma;
+            const double z = gamma / sigma;
+            const double cdf = 0.5 * std::erfc(-z / std::sqrt(2.0));
+            const double pdf = std::exp(-0.5 * z * z) / std::sqrt(2.0 * std::acos(-1.0));
+            return cdf * d_mu + pdf * d_sigma;
+        }
+
+        // Squared exponential kernel of the regression, with its current hyperparameters
+        double kernel(const VectorXd& x_1, const VectorXd& x_2) const
+        {
+            const double r = getRegressor().r;
+            return getRegressor().a * std::exp(-0.5 * (x_1 - x_2).squaredNorm() / (r * r));
+        }
+
+        // Kernel between x and every data point
+        VectorXd kernelVector(const VectorXd& x) const
+        {
+            const MatrixXd& X = getRegressor().X;
+            VectorXd k(X.cols());
+            for (int i = 0; i < X.cols(); ++i) k(i) = kernel(x, X.col(i));
+            return k;
+        }
+
+        // Joint posterior covariance of the latent function at the columns of points
+        MatrixXd posteriorCovariance(const MatrixXd& points) const
+        {
+            const int n = points.cols();
+
+            MatrixXd prior(n, n);
+            MatrixXd k(getRegressor().X.cols(), n);
+            for (int i = 0; i < n; ++i)
+            {
+                for (int j = 0; j < n; ++j) prior(i, j) = kernel(points.col(i), points.col(j));
+                k.col(i) = kernelVector(points.col(i));
+            }
+            return prior - k.transpose() * getRegressor().C_inv * k;
+        }
+
+        // Point number index of the Halton sequence in the unit cube
+        VectorXd haltonPoint(size_t index) const
+        {
+            VectorXd x(dimension);
+            size_t base = 1;
+            for (size_t d = 0; d < dimension; ++d)
+            {
+                // Next prime as the base of this coordinate
+                bool prime = false;
+                while (!prime)
+                {
+                    ++base;
+                    prime = true;
+                    for (size_t p = 2; p * p <= base; ++p) prime = prime && base % p != 0;
+                }
+
+                double f = 1.0, r = 0.0;
+                for (size_t i = index; i > 0; i /= base)
+                {
+                    f /= base;
+                    r += f * (i % base);
+                }
+                x(d) = r;
+            }
+            return x;
+        }
+
+        // Maximize f over the unit cube without any randomness: start from the best of the data
+        // points and a fixed set of Halton points, then refine with a compass search
+        template <typename F>
+        VectorXd maximizeDeterministically(F f) const
+        {
+            VectorXd best = haltonPoint(1);
+            double best_value = f(best);
+            auto consider = [&](const VectorXd& x)
+            {
+                const double value = f(x);
+                if (value > best_value)
+                {
+                    best = x;
+                    best_value = value;
+                }
+            };
+
+            for (int i = 0; i < getRegressor().X.cols(); ++i) consider(getRegressor().X.col(i));
+            for (size_t i = 2; i <= 64 * dimension; ++i) consider(haltonPoint(i));
+
+            return localSearch(f, best);
+        }
+
+        // Compass search for a local maximum of f in the unit cube, starting from start
+        template <typename F>
+        VectorXd localSearch(F f, const VectorXd& start) const
+        {
+            VectorXd best = start;
+            double best_value = f(best);
+            auto consider = [&](const VectorXd& x)
+            {
+                const double value = f(x);
+                if (value > best_value)
+                {
+                    best = x;
+                    best_value = value;
+                }
+            };
+
+            for (double step = 0.1; step > 1e-4; step /= 2.0)
+            {
+                bool improved = true;
+                while (improved)
+                {
+                    improved = false;
+                    for (size_t d = 0; d < dimension; ++d)
+                    {
+                        for (const double sign : { -1.0, 1.0 })
+                        {
+                            VectorXd x = best;
+                            x(d) = std::min(std::max(x(d) + sign * step, 0.0), 1.0);
+                            const double previous = best_value;
+                            consider(x);
+                            improved = improved || best_value > previous;
+                        }
+                    }
+                }
+            }
+            return best;
+        }
+
+#ifndef SLS_NO_NLOPT
+        // The C++ library draws from std::rand, so a seeded framework reseeds it and keeps other
+        // seeded frameworks from drawing in between
+        VectorXd seededFindNextPoint() const
+        {
+            if (!seeded) return acquisition_function::FindNextPoint(getRegressor());
+            static std::mutex global_rng;
+            const std::lock_guard<std::mutex> lock(global_rng);
+            std::srand(randomSeed());
+            return acquisition_function::FindNextPoint(getRegressor());
+        }
+
+        VectorXd findNextPoint()
+        {
+            const auto start = std::chrono::steady_clock::now();
+
+            VectorXd best = seededFindNextPoint();
+            double best_value = acquisitionValue(best);
+            for (size_t i = 1; i < acquisition_restarts; ++i)
+            {
+                const std::chrono::duration<double> elapsed = std::chrono::steady_clock::now() - start;
+                if (elapsed.count() > acquisition_timeout) break;
+
+                const VectorXd x = seededFindNextPoint();
+                const double value = acquisitionValue(x);
+                if (value > best_value)
+                {
+                    best = x;
+                    best_value = value;
+                }
+            }
+
+            // The C++ library always maximizes the expected improvement without xi
+            if (acquisition_param != 0.0)
+            {
+                best = localSearch([&](const VectorXd& x) { return acquisitionValue(x); }, best);
+            }
+            return best;
+        }
+#else
+        // Without nlopt: the deterministic search over a grid of Halton points, followed by
+        // local searches from random starting points
+        VectorXd findNextPoint()
+        {
+            const auto start = std::chrono::steady_clock::now();
+            const auto f = [&](const VectorXd& x) { return acquisitionValue(x); };
+
+            VectorXd best = maximizeDeterministically(f);
+            double best_value = f(best);
+            for (size_t i = 1; i < acquisition_restarts; ++i)
+            {
+                const std::chrono::duration<double> elapsed = std::chrono::steady_clock::now() - start;
+                if (elapsed.count() > acquisition_timeout) break;
+
+                const VectorXd x = localSearch(f, randomVector());
+                const double value = f(x);
+                if (value > best_value)
+                {
+                    best = x;
+                    best_value = value;
+                }
+            }
+            return best;
+        }
+#endif
+
+        double scheduledAcquisitionParam() const
+        {
+            // The first slider chosen by the acquisition function comes after the first step
+            const size_t steps = num_iterations > 0 ? num_iterations - 1 : 0;
+            if (steps >= schedule_over) return schedule_end;
+            const double t = double(steps) / schedule_over;
+            return schedule_start + t * (schedule_end - schedule_start);
+        }
+
+        // The end of the slider that stays close to what is known to be good
+        VectorXd findAnchor()
+        {
+            if (fixed_anchor.size() > 0) return fixed_anchor;
+            if (anchor_mode == 1 && step_point.size() > 0) return step_point;
+
+#ifndef SLS_NO_NLOPT
+            if (!custom_acquisition && !deterministic_acquisition) return getRegressor().find_arg_max();
+#endif
+            return maximizeDeterministically([&](const VectorXd& x) { return getRegressor().estimate_y(x); });
+        }
+
+        void updateSliderEnds()
+        {
+            // If this is the first time...
+            if (x_max.rows() == 0)
+            {
+                if (skip_initial_slider)
+                {
+                    replaceSlider(nullptr);
+                }
+                else
+                {
+                    setInitialSlider();
+                    slider_acquisition_point = slider->orig_1;
+                }
+                return;
+            }
+
+            // ...or we're still warming up, unless a candidate was given
+            const bool has_candidate = next_candidate.size() > 0;
+            if (num_iterations < warmup_steps && !has_candidate)
+            {
+                if (fixed_anchor.size() > 0)
+                {
+                    replaceSlider(std::make_shared<Slider>(fixed_anchor, randomVector(), false));
+                }
+                else
+                {
+                    replaceSlider(std::make_shared<Slider>(randomVector(), randomVector(), true));
+                }
+                slider_acquisition_point = slider->orig_1;
+                return;
+            }
+
+            if (has_schedule) acquisition_param = scheduledAcquisitionParam();
+
+            const VectorXd x_1 = findAnchor();
+            VectorXd x_next;
+            if (has_candidate)
+            {
+                x_next = next_candidate;
+                next_candidate = VectorXd::Zero(0);
+            }
+            else if (custom_acquisition)
+            {
+                x_next = maximizeDeterministically([&](const VectorXd& x)
+                {
+                    return custom_acquisition(getRegressor().estimate_y(x), getRegressor().estimate_s(x));
+                });
+            }
+            else if (deterministic_acquisition)
+            {
+                x_next = maximizeDeterministically([&](const VectorXd& x) { return acquisitionValue(x); });
+            }
+            else
+            {
+                x_next = findNextPoint();
+            }
+
+            // Shrink the slider towards the anchor
+            const double scale = std::pow(range_decay, num_iterations);
+            const VectorXd x_2 = x_1 + scale * (x_next - x_1);
+
+            if (fixed_anchor.size() > 0)
+            {
+                // The slider starts exactly at the anchor, which is never perturbed
+                setSlider(x_1, endpoint_jitter > 0.0 ? jitter(x_2) : x_2, false);
+            }
+            else if (endpoint_jitter > 0.0)
+            {
+                setSlider(jitter(x_1), jitter(x_2));
+            }
+            else
+            {
+                setSlider(x_1, x_2);
+            }
+            slider_acquisition_point = x_next;
+        }
+
+        // Gaussian perturbation, kept in the unit cube
+        VectorXd jitter(const VectorXd& x) const
+        {
+            // Seeded from std::rand if unseeded, so that seeding the C++ library covers this too
+            std::mt19937 gen(randomSeed());
+            std::normal_distribution<double> normal(0.0, endpoint_jitter);
+            VectorXd y = x;
+            for (Index d = 0; d < y.size(); ++d) y(d) = std::min(std::max(y(d) + normal(gen), 0.0), 1.0);
+            return y;
+        }
+
+        // Every change of the slider goes through here, to keep track of its generation
+        void replaceSlider(const std::shared_ptr<Slider>& new_slider)
+        {
+            slider = new_slider;
+            slider_acquisition_point = VectorXd::Zero(0);
+            ++slider_generation;
+        }
+
+        void setInitialSlider()
+        {
+            switch (initial_strategy)
+            {
+                case 1:
+                {
+                    // Reflect a random point through the center of the unit cube
+                    const VectorXd x = randomVector();
+                    setSlider(x, VectorXd::Ones(dimension) - x);
+                    break;
+                }
+                case 2:
+                    // The given points are the ends, so don't enlarge the slider
+                    setSlider(initial_end_0, initial_end_1, false);
+                    break;
+                default:
+                    replaceSlider(std::make_shared<Slider>(randomVector(), randomVector(), true));
+            }
+        }
+
+        void setSlider(const VectorXd& x_1, const VectorXd& x_2, bool enlarge = true)
+        {
+            // Once the model is confident, both ends can coincide, which would make every
+            // position on the slider the same point. Nudge the second end in a random direction.
+            slider_nudged = (x_2 - x_1).norm() < 1e-8;
+            if (slider_nudged)
+            {
+                VectorXd direction = randomVector() - VectorXd::Constant(dimension, 0.5);
+                replaceSlider(std::make_shared<Slider>(x_1, x_1 + 0.01 * direction.normalized(), enlarge));
+                return;
+            }
+
+            replaceSlider(std::make_shared<Slider>(x_1, x_2, enlarge));
+        }
+
+        const VectorXd computeParametersFromSlider(double value)
+        {
+            // Positions beyond the ends extrapolate linearly, as far as the overshoot allows
+            value = std::min(std::max(value, -slider_overshoot), 1.0 + slider_overshoot);
+            if (value >= 0.0 && value <= 1.0) value = warpPosition(value);
+            return snapCategories(slider->end_0 * (1.0 - value) + slider->end_1 *  value);
+        }
+
+        // Set the largest coordinate of each one-hot group to 1 and the others to 0
+        VectorXd snapCategories(VectorXd x) const
+        {
+            for (const auto& group : categorical_groups)
+            {
+                size_t best = group[0];
+                for (const size_t d : group) if (x(d) > x(best)) best = d;
+                for (const size_t d : group) x(d) = d == best ? 1.0 : 0.0;
+            }
+            return x;
+        }
+
+        double warpPosition(double value) const
+        {
+            if (slider_warp == 1.0) return value;
+
+            // Position of the anchor (the current best) on the slider
+            const VectorXd d = slider->end_1 - slider->end_0;
+            const double t = std::min(std::max(d.dot(slider->orig_0 - slider->end_0) / d.squaredNorm(), 0.0), 1.0);
+
+            if (value >= t)
+            {
+                return t >= 1.0 ? value : t + (1.0 - t) * std::pow((value - t) / (1.0 - t), slider_warp);
+            }
+            return t - t * std::pow((t - value) / t, slider_warp);
+        }
+
+        VectorXd proceedOptimization(double slider_position)
+        {
+            const VectorXd x = recordOptimization(slider_position);
+            finishStep();
+            return x;
+        }
+
+        // The first half of proceedOptimization, leaves the slider untouched
+        VectorXd recordOptimization(double slider_position)
+        {
+            const VectorXd x = computeParametersFromSlider(slider_position);
+            step_position = slider_position;
+            step_triple = false;
+            recordPreference(x, { slider->orig_0, slider->orig_1 });
+            return x;
+        }
+
+        // best indexes the positions 0, 0.5 and 1 on the slider
+        VectorXd proceedOptimizationTriple(size_t best)
+        {
+            std::vector<VectorXd> xs = {
+                computeParametersFromSlider(0.0),
+                computeParametersFromSlider(0.5),
+                computeParametersFromSlider(1.0),
+            };
+            const VectorXd x = xs[best];
+            xs.erase(xs.begin() + best);
+            step_position = 0.5 * best;
+            step_triple = true;
+            proceedWithPreference(x, xs);
+            return x;
+        }
+
+        void proceedWithPreference(const VectorXd& x, const std::vector<VectorXd>& others)
+        {
+            recordPreference(x, others);
+            finishStep();
+        }
+
+        void recordPreference(const VectorXd& x, std::vector<VectorXd> others)
+        {
+            // The slider origins come from the acquisition search and can lie between categories
+            for (VectorXd& other : others) other = snapCategories(other);
+
+            previous_slider = slider;
+            previous_data = data;
+            previous_y_max = y_max;
+            previous_regressor = regressor;
+            step_slider = slider;
+            step_point = x;
+            step_acquisition_point = slider_acquisition_point;
+            slider_history.push_back(slider);
+
+            // Add new preference data
+            addPreference(x, others);
+            step_indices = { nearestColumn(x) };
+            for (const VectorXd& other : others) step_indices.push_back(nearestColumn(other));
+
+            // Compute regression
+            const auto start = std::chrono::steady_clock::now();
+            computeRegression();
+            const auto regressed = std::chrono::steady_clock::now();
+            regression_ns = std::chrono::duration_cast<std::chrono::nanoseconds>(regressed - start).count();
+
+            // Check the current best
+            updateBest();
+            step_x_max = x_max;
+            step_y_max = y_max;
+            best_history.push_back(y_max);
+            ++num_iterations;
+            if (on_new_best && (std::isnan(previous_y_max) || y_max > previous_y_max))
+            {
+                on_new_best(x_max, y_max);
+            }
+        }
+
+        // The second half of a step, after the preference was recorded
+        void finishStep()
+        {
+            const auto start = std::chrono::steady_clock::now();
+            updateSliderEnds();
+            const auto updated = std::chrono::steady_clock::now();
+            acquisition_ns = std::chrono::duration_cast<std::chrono::nanoseconds>(updated - start).count();
+
+            checkHealth();
+        }
+
+        void addPreference(const VectorXd& x, const std::vector<VectorXd>& others)
+        {
+            data.AddNewPoints(x, others);
+
+            // Data merges close points, a point merged with the chosen one says nothing about it
+            Preference& p = data.D.back();
+            const auto preferred = p[0];
+            p.erase(std::remove(p.begin() + 1, p.end(), preferred), p.end());
+            if (p.size() < 2) data.D.pop_back();
+        }
+
+        // Points closer than the merge threshold of Data are stored as one column
+        size_t nearestColumn(const VectorXd& x) const
+        {
+            size_t index;
+            (data.X.colwise() - x).colwise().squaredNorm().minCoeff(&index);
+            return index;
+        }
+
+        void checkHealth()
+        {
+            const bool hyperparameters_valid =
+                std::isfinite(getRegressor().a) && getRegressor().a > 0.0 &&
+                std::isfinite(getRegressor().r) && getRegressor().r > 0.0 &&
+                std::isfinite(getRegressor().b) && getRegressor().b >= 0.0;
+
+            if (use_map_hyperparameters && !hyperparameters_valid)
+            {
+                health_warning = 1;
+            }
+            else if (conditionNumber() > condition_threshold)
+            {
+                health_warning = 2;
+            }
+            else
+            {
+                health_warning = slider_nudged ? 3 : 0;
+            }
+        }
+
+        double conditionNumber() const
+        {
+            if (getRegressor().C.rows() == 0) return 1.0;
+
+            const SelfAdjointEigenSolver<MatrixXd> solver(getRegressor().C, EigenvaluesOnly);
+            const double min = solver.eigenvalues().minCoeff();
+            const double max = solver.eigenvalues().maxCoeff();
+            return min > 0.0 ? max / min : INFINITY;
+        }
+
+        // Forget the steps taken, but not the data, the regression and the slider
+        void clearSession()
+        {
+            x_max = VectorXd::Zero(0);
+            y_max = NAN;
+            num_iterations = 0;
+            previous_slider = nullptr;
+            previous_data = Data();
+            previous_y_max = NAN;
+            previous_regressor = nullptr;
+            step_slider = nullptr;
+            step_position = NAN;
+            step_triple = false;
+            step_point = VectorXd::Zero(0);
+            step_acquisition_point = VectorXd::Zero(0);
+            step_x_max = VectorXd::Zero(0);
+            step_y_max = NAN;
+            step_indices.clear();
+            slider_history.clear();
+            best_history.clear();
+            absolute_observations.clear();
+            health_warning = 0;
+            regression_ns = 0;
+            acquisition_ns = 0;
+        }
+
+        void reset()
+        {
+            data = Data();
+            clearSession();
+
+            computeRegression();
+            updateSliderEnds();
+        }
+
+        void restartKeepHyperparameters()
+        {
+            kernel_a = getRegressor().a;
+            kernel_r = getRegressor().r;
+            kernel_b = getRegressor().b;
+            use_map_hyperparameters = false;
+            reset();
+        }
+
+        void amendLastComparison(double slider_position)
+        {
+            data = previous_data;
+            replaceSlider(previous_slider);
+            slider_acquisition_point = step_acquisition_point;
+            y_max = previous_y_max;
+            regressor = previous_regressor;
+            --num_iterations;
+            slider_history.pop_back();
+            best_history.pop_back();
+            proceedOptimization(slider_position);
+        }
+
+        void addAbsoluteObservation(const VectorXd& x, double score)
+        {
+            for (const auto& observation : absolute_observations)
+            {
+                if (score > observation.second) addPreference(x, { observation.first });
+                if (score < observation.second) addPreference(observation.first, { x });
+            }
+            absolute_observations.emplace_back(x, score);
+            previous_slider = nullptr;
+            step_indices.clear();
+            refit();
+        }
+
+        // Recompute the regression for changed data or hyperparameters
+        void refit()
+        {
+            computeRegression();
+            if (data.X.cols() > 0) updateBest();
+        }
+
+        void updateBest()
+        {
+            unsigned index;
+            y_max = getRegressor().y.maxCoeff(&index);
+            x_max = getRegressor().X.col(index);
+        }
+
+        void removePoint(size_t index)
+        {
+            const size_t n = data.X.cols();
+
+            MatrixXd X(data.X.rows(), n - 1);
+            for (size_t j = 0, k = 0; j < n; ++j)
+            {
+                if (j != index) X.col(k++) = data.X.col(j);
+            }
+            data.X = X;
+
+            std::vector<Preference> D;
+            for (const Preference& p : data.D)
+            {
+                // A preference for the removed point says nothing about the others
+                if (static_cast<size_t>(p[0]) == index) continue;
+
+                Preference q;
+                for (const auto i : p)
+                {
+                    if (static_cast<size_t>(i) == index) continue;
+                    q.push_back(static_cast<size_t>(i) > index ? i - 1 : i);
+                }
+                if (q.size() > 1) D.push_back(q);
+            }
+            data.D = D;
+            previous_slider = nullptr;
+            step_indices.clear();
+
+            computeRegression();
+            if (data.X.cols() > 0)
+            {
+                updateBest();
+            }
+            else
+            {
+                x_max = VectorXd::Zero(0);
+                y_max = NAN;
+            }
+        }
+
+    };
+}}
+
+cpp_class!(
+    /// State of the sequential line search algorithm
+    ///
+    /// Cloning copies the whole state, the clone then continues independently.
+    pub unsafe struct SLSFramework as "SLSFramework"
+);
+
+/// Set the number of threads Eigen uses for its linear algebra.
+///
+/// This applies to the whole process, i.e. to all frameworks. `0` restores Eigen's default of
+/// using all cores. A server running many sessions at once is usually better off with `1`, a
+/// single session with a lot of data benefits from more. Eigen only parallelizes if the C++ code
+/// is compiled with OpenMP, which the build script does not enable, otherwise this has no effect.
+pub fn set_eigen_threads(n: usize) {
+    let n = n as i32;
+    unsafe {
+        cpp!([n as "int"] {
+            Eigen::setNbThreads(n);
+        });
+    }
+}
+
+#[cfg(test)]
+fn make_deterministic(sls: &mut SLSFramework, start: &(Vec<f64>, Vec<f64>)) {
+    sls.set_deterministic_acquisition(true);
+    sls.set_warmup_steps(0);
+    sls.set_initial_strategy(InitialStrategy::FixedEndpoints(start.0.clone(), start.1.clone())).unwrap();
+}
+
+#[cfg(test)]
+fn live_frameworks() -> i64 {
+    unsafe {
+        cpp!([] -> i64 as "int64_t" {
+            return InstanceCounter::live;
+        })
+    }
+}
+
+unsafe fn as_rust_vec(ev: *const u8) -> Vec<f64> {
+    let dim = cpp!([ev as "const VectorXd*"] -> usize as "size_t" {
+            return ev->rows();
+        });
+
+    let mut res = vec![0.0f64; dim];
+    let ptr = res.as_mut_ptr();
+    cpp!([ev as "const VectorXd*", dim as "size_t", ptr as "double*"] {
+        for (size_t i = 0; i < dim; ++i) {
+            ptr[i] = (*ev)(i);
+        }
+    });
+    res
+}
+
+unsafe fn take_cpp_string(s: *const u8) -> String {
+    let c_str = cpp!([s as "const std::string *"] -> *const c_char as "const char *" {
+            return s->c_str();
+        });
+    let res = CStr::from_ptr(c_str).to_string_lossy().into_owned();
+    cpp!([s as "const std::string *"] {
+        delete s;
+    });
+    res
+}
+
+unsafe fn as_rust_matrix(em: *const u8) -> Vec<Vec<f64>> {
+    let rows = cpp!([em as "const MatrixXd*"] -> usize as "size_t" {
+            return em->rows();
+        });
+    let cols = cpp!([em as "const MatrixXd*"] -> usize as "size_t" {
+            return em->cols();
+        });
+
+    let mut res = vec![0.0f64; rows * cols];
+    let ptr = res.as_mut_ptr();
+    cpp!([em as "const MatrixXd*", rows as "size_t", cols as "size_t", ptr as "double*"] {
+        for (size_t j = 0; j < cols; ++j) {
+            for (size_t i = 0; i < rows; ++i) {
+                ptr[j * rows + i] = (*em)(i, j);
+            }
+        }
+    });
+    (0..cols).map(|j| res[j * rows..(j + 1) * rows].to_vec()).collect()
+}
+
+impl SLSFramework {
+    /// Initializes the algorithm.
+    ///
+    /// `dim` (`> 0`) is the number of dimensions of the parameter space
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is zero or the C++ library fails, see [try_new](SLSFramework::try_new).
+    pub fn new(dim: usize) -> Self {
+        match SLSFramework::try_new(dim) {
+            Ok(sls) => sls,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Initializes the algorithm, failing if `dim` is zero or the C++ library throws.
+    ///
+    /// See [new](SLSFramework::new).
+    pub fn try_new(dim: usize) -> Result<Self, SLSError> {
+        SLSFramework::try_create(dim, None)
+    }
+
+    /// Initializes the algorithm with its own random number generator.
+    ///
+    /// All randomness of the framework is derived from `seed`: the initial slider, random
+    /// sliders during [warm-up](SLSFramework::set_warmup_steps), nudged sliders,
+    /// [jitter](SLSFramework::set_endpoint_jitter) and the random starting points of the
+    /// acquisition search. The same `seed` and the same answers give the same session, no
+    /// matter how many other frameworks run at the same time, and the global random number
+    /// generator of the C++ library is left alone. Clones continue with a copy of the generator,
+    /// so they draw the same numbers as the original.
+    ///
+    /// The acquisition search of the C++ library (without
+    /// [deterministic acquisition](SLSFramework::set_deterministic_acquisition)) only knows the
+    /// global generator. It is reseeded from `seed` before every search, under a lock shared by
+    /// all seeded frameworks, but frameworks created with [new](SLSFramework::new) searching at
+    /// the same time can still draw from it in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is zero or the C++ library fails, see [try_new](SLSFramework::try_new).
+    pub fn new_with_rng(dim: usize, seed: u64) -> Self {
+        match SLSFramework::try_create(dim, Some(seed)) {
+            Ok(sls) => sls,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    fn try_create(dim: usize, seed: Option<u64>) -> Result<Self, SLSError> {
+        if dim == 0 {
+            return Err(SLSError::ZeroDimensions);
+        }
+        unsafe {
+            let mut sls = cpp!([dim as "size_t"] -> SLSFramework as "SLSFramework" {
+                return SLSFramework(dim);
+            });
+            let fw = &mut sls;
+            if let Some(seed) = seed {
+                cpp!([fw as "SLSFramework*", seed as "uint64_t"] {
+                    fw->seed(seed);
+                });
+            }
+            let error = cpp!([fw as "SLSFramework*"] -> *const u8 as "const std::string *" {
+                try {
+                    fw->initialize();
+                    return nullptr;
+                } catch (const std::exception& e) {
+                    return new std::string(e.what());
+                } catch (...) {
+                    return new std::string("unknown exception");
+                }
+            });
+            if !error.is_null() {
+                return Err(SLSError::Cpp(take_cpp_string(error)));
+            }
+            Ok(sls)
+        }
+    }
+
+    /// Initializes the algorithm for a session of about `expected_iterations` steps.
+    ///
+    /// Behaves exactly like [new](SLSFramework::new), but reserves space for that many steps up
+    /// front where the C++ data structures allow it. This is only a hint: the sampled points
+    /// are stored in an Eigen matrix, which can't reserve space and is still reallocated as it
+    /// grows, only the list of preferences and the slider history are reserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is zero or the C++ library fails, see [try_new](SLSFramework::try_new).
+    pub fn new_with_capacity(dim: usize, expected_iterations: usize) -> Self {
+        let mut sls = SLSFramework::new(dim);
+        let fw = &mut sls;
+        unsafe {
+            cpp!([fw as "SLSFramework*", expected_iterations as "size_t"] {
+                fw->data.D.reserve(expected_iterations);
+                fw->slider_history.reserve(expected_iterations);
+            });
+        }
+        sls
+    }
+
+    /// Set the number of sliders with random endpoints.
+    ///
+    /// The first `n` sliders are chosen at random instead of from the regression, which is not
+    /// very reliable with only a few data points. The default is `1`, i.e. only the initial slider
+    /// is random. The initial slider is always random, even for `n = 0`.
+    pub fn set_warmup_steps(&mut self, n: usize) {
+        unsafe {
+            cpp!([self as "SLSFramework*", n as "size_t"] {
+                self->warmup_steps = n;
+            });
+        }
+    }
+
+    /// Shrink the sliders towards the current best over time.
+    ///
+    /// After `k` steps, the slider spans `x_best + factor^k * (x_next - x_best)`, where `x_best`
+    /// is the inferred maximum and `x_next` the point chosen by the acquisition function. The
+    /// slider therefore still points in the direction the acquisition function suggests, but
+    /// only reaches `x_next` itself while `factor^k` is close to one. Random warm-up sliders are
+    /// not affected.
+    ///
+    /// `factor` (`0 < factor <= 1`) defaults to `1`, which disables shrinking.
+    pub fn set_range_decay(&mut self, factor: f64) {
+        assert!(factor > 0. && factor <= 1., "range decay factor must be in (0, 1]");
+        unsafe {
+            cpp!([self as "SLSFramework*", factor as "double"] {
+                self->range_decay = factor;
+            });
+        }
+    }
+
+    /// Estimate the kernel hyperparameters from the data.
+    ///
+    /// If enabled, each regression uses MAP estimates of the hyperparameters instead of the fixed
+    /// defaults. This gives a better model, but makes every step slower. Disabled by default.
+    pub fn set_use_map_hyperparameters(&mut self, enabled: bool) {
+        unsafe {
+            cpp!([self as "SLSFramework*", enabled as "bool"] {
+                self->use_map_hyperparameters = enabled;
+            });
+        }
+    }
+
+    /// Restrict the hyperparameters estimated with MAP estimation.
+    ///
+    /// Each argument is an inclusive `(min, max)` range for the length scale, the signal variance
+    /// and the noise level (the [regularization](SLSFramework::get_regularization)). On small
+    /// datasets the estimation can end up at degenerate values, like a length scale close to zero,
+    /// which make sliders erratic. The estimation itself can't be constrained, so estimates
+    /// outside of a range are clamped to it and the regression is fitted again with the clamped
+    /// values fixed. The model is refitted right away, so the current fit respects new bounds.
+    ///
+    /// By default all ranges are `(0, inf)`, i.e. unrestricted. Length scales of about
+    /// `(0.05, 2)` are sensible in the unit cube. Has no effect without MAP estimation.
+    pub fn set_hyperparameter_bounds(
+        &mut self,
+        length_scale: (f64, f64),
+        signal_var: (f64, f64),
+        noise: (f64, f64),
+    ) {
+        for &(min, max) in &[length_scale, signal_var, noise] {
+            assert!((0. ..=max).contains(&min), "hyperparameter bounds must satisfy 0 <= min <= max");
+        }
+        let (min_r, max_r) = length_scale;
+        let (min_a, max_a) = signal_var;
+        let (min_b, max_b) = noise;
+        unsafe {
+            cpp!([self as "SLSFramework*", min_r as "double", max_r as "double", min_a as "double", max_a as "double", min_b as "double", max_b as "double"] {
+                self->min_r = min_r;
+                self->max_r = max_r;
+                self->min_a = min_a;
+                self->max_a = max_a;
+                self->min_b = min_b;
+                self->max_b = max_b;
+                self->refit();
+            });
+        }
+    }
+
+    /// Get the regularization of the latent function
+    ///
+    /// This is the variance added to the diagonal of the kernel matrix of the regression (the
+    /// `b` hyperparameter of the C++ library), i.e. numerical jitter on the latent scores. It is
+    /// not the noise of the user's answers, which the preference likelihood models separately.
+    pub fn get_regularization(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->getRegressor().b;
+            })
+        }
+    }
+
+    /// Set the regularization of the latent function
+    ///
+    /// See [get_regularization](SLSFramework::get_regularization). The default is `0.005`.
+    /// Smaller values let the regression follow the data more closely, which sharpens the optimum
+    /// on nearly noise free objectives but makes the kernel matrix worse conditioned. The
+    /// regularization is also part of the predicted variance, so it sets a lower bound for
+    /// [predict](SLSFramework::predict) at the data points. The regression is recomputed. With
+    /// [MAP estimation](SLSFramework::set_use_map_hyperparameters) enabled, it is estimated from
+    /// the data instead.
+    pub fn set_regularization(&mut self, b: f64) {
+        assert!(b >= 0., "regularization must not be negative");
+        unsafe {
+            cpp!([self as "SLSFramework*", b as "double"] {
+                self->kernel_b = b;
+                self->refit();
+            });
+        }
+    }
+
+    /// Release memory of a finished session.
+    ///
+    /// Drops the regression with its kernel matrices and the state kept for
+    /// [amend_last_comparison](SLSFramework::amend_last_comparison). The best point, its score,
+    /// the data and all settings are kept, so the next method that needs the regression (any
+    /// prediction, or further steps) rebuilds it once from the data. With
+    /// [MAP estimation](SLSFramework::set_use_map_hyperparameters) enabled, the rebuild estimates
+    /// the hyperparameters again.
+    pub fn finalize(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->finalize();
+            });
+        }
+    }
+
+    /// Compute the regression now, if it is missing.
+    ///
+    /// The regression is kept up to date by every change of the data, the only time it is
+    /// missing is after [finalize](SLSFramework::finalize). Calling this then pays the cost of
+    /// rebuilding it at a known time instead of in the next predictive call. A no-op otherwise.
+    pub fn compute_regression(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->getRegressor();
+            });
+        }
+    }
+
+    /// Set how often the acquisition function is maximized for each slider.
+    ///
+    /// The maximization starts from random points, so restarting it can find a better next
+    /// point. The best result of all `restarts` (`>= 1`) is used. The default is `1`.
+    pub fn set_acquisition_restarts(&mut self, restarts: usize) {
+        assert!(restarts >= 1, "at least one acquisition run is required");
+        unsafe {
+            cpp!([self as "SLSFramework*", restarts as "size_t"] {
+                self->acquisition_restarts = restarts;
+            });
+        }
+    }
+
+    /// Limit the time spent on acquisition restarts.
+    ///
+    /// No further restart is started once `timeout` has passed, although the first run always
+    /// completes. `None`, the default, allows all restarts to run.
+    pub fn set_acquisition_timeout(&mut self, timeout: Option<Duration>) {
+        let seconds = timeout.map_or(f64::INFINITY, |t| t.as_secs_f64());
+        unsafe {
+            cpp!([self as "SLSFramework*", seconds as "double"] {
+                self->acquisition_timeout = seconds;
+            });
+        }
+    }
+
+    /// Devote more of the slider to the region around the current best.
+    ///
+    /// With `t` the position of the current best on the slider, a position `pos >= t` is mapped
+    /// to `t + (1 - t) * ((pos - t) / (1 - t))^exponent`, and `pos < t` symmetrically. The ends
+    /// still map to `0` and `1`, and an `exponent > 1` spreads the parameters close to the best
+    /// over a larger part of the slider. This applies to both
+    /// [get_parameters_from_slider](SLSFramework::get_parameters_from_slider) and
+    /// [proceed_optimization](SLSFramework::proceed_optimization).
+    ///
+    /// `exponent` (`> 0`) defaults to `1`, which keeps the slider linear. For random sliders the
+    /// first of the two random points takes the place of the current best.
+    pub fn set_slider_warp(&mut self, exponent: f64) {
+        assert!(exponent > 0., "slider warp exponent must be positive");
+        unsafe {
+            cpp!([self as "SLSFramework*", exponent as "double"] {
+                self->slider_warp = exponent;
+            });
+        }
+    }
+
+    /// Choose sliders without any randomness.
+    ///
+    /// Instead of nlopt with random restarts, the regression and the acquisition function are
+    /// maximized by evaluating them at the data points and a fixed set of quasi-random points,
+    /// followed by a local compass search. The same data then always gives the same slider,
+    /// which helps with snapshot tests and reproducing reports. The maxima found this way are
+    /// usually less accurate, especially in high dimensions. Random warm-up sliders are not
+    /// affected. Disabled by default.
+    pub fn set_deterministic_acquisition(&mut self, enabled: bool) {
+        unsafe {
+            cpp!([self as "SLSFramework*", enabled as "bool"] {
+                self->deterministic_acquisition = enabled;
+            });
+        }
+    }
+
+    /// Set the exploration parameter of the acquisition function.
+    ///
+    /// Sliders are chosen by maximizing the expected improvement of the latent score over the
+    /// current best. `xi` is subtracted from the predicted improvement: larger values favor
+    /// uncertain regions (exploration), negative ones points close to the best (exploitation).
+    /// The default is `0`, small values like `0.01` already have a noticeable effect.
+    ///
+    /// nlopt only optimizes the expected improvement for `xi = 0`, for other values its result
+    /// is refined by a local search. Replaces any
+    /// [schedule](SLSFramework::set_acquisition_schedule).
+    pub fn set_acquisition_param(&mut self, xi: f64) {
+        unsafe {
+            cpp!([self as "SLSFramework*", xi as "double"] {
+                self->acquisition_param = xi;
+                self->has_schedule = false;
+            });
+        }
+    }
+
+    /// Let positions go beyond the ends of the slider.
+    ///
+    /// Positions in `[-frac, 1 + frac]` are accepted by
+    /// [get_parameters_from_slider](SLSFramework::get_parameters_from_slider) and
+    /// [proceed_optimization](SLSFramework::proceed_optimization), positions outside of `[0, 1]`
+    /// extrapolate linearly along the slider. This lets the user push further in the preferred
+    /// direction, and the extrapolated point is what gets recorded. Such points can leave the
+    /// unit cube the regression is optimized over, where the model knows little. Positions
+    /// outside the extended range are clamped to it.
+    ///
+    /// `frac` (`>= 0`) defaults to `0`, which clamps positions to `[0, 1]`.
+    pub fn set_slider_overshoot(&mut self, frac: f64) {
+        assert!(frac >= 0., "slider overshoot must not be negative");
+        unsafe {
+            cpp!([self as "SLSFramework*", frac as "double"] {
+                self->slider_overshoot = frac;
+            });
+        }
+    }
+
+    /// Set how far positions may be off to still count as valid.
+    ///
+    /// Slider widgets often report positions like `1.0000001`. Positions within `eps` (`>= 0`)
+    /// of `0` or `1` are treated as exactly `0` or `1`, and positions at most `eps` beyond the
+    /// valid range (`[0, 1]`, extended by the [overshoot](SLSFramework::set_slider_overshoot))
+    /// as its end. Positions further out are clamped by
+    /// [proceed_optimization](SLSFramework::proceed_optimization) and
+    /// [get_parameters_from_slider](SLSFramework::get_parameters_from_slider), but rejected by
+    /// their `try_` variants. The default is `1e-6`.
+    pub fn set_position_tolerance(&mut self, eps: f64) {
+        assert!(eps >= 0., "position tolerance must not be negative");
+        unsafe {
+            cpp!([self as "SLSFramework*", eps as "double"] {
+                self->position_tolerance = eps;
+            });
+        }
+    }
+
+    fn slider_overshoot(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->slider_overshoot;
+            })
+        }
+    }
+
+    // None if pos is further out of range than the tolerance
+    fn snap_position(&self, pos: f64) -> Option<f64> {
+        let eps = unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->position_tolerance;
+            })
+        };
+        let overshoot = self.slider_overshoot();
+        if !(-overshoot - eps..=1. + overshoot + eps).contains(&pos) {
+            return None;
+        }
+        if pos.abs() <= eps {
+            Some(0.)
+        } else if (pos - 1.).abs() <= eps {
+            Some(1.)
+        } else {
+            Some(pos.max(-overshoot).min(1. + overshoot))
+        }
+    }
+
+    // Clamps instead of failing, NaN stays NaN and is rejected by the try_ variants
+    fn clamp_position(&self, pos: f64) -> f64 {
+        self.snap_position(pos).unwrap_or_else(|| {
+            let overshoot = self.slider_overshoot();
+            pos.max(-overshoot).min(1. + overshoot)
+        })
+    }
+
+    /// Take one step in the algorithm.
+    ///
+    /// `pos` (`0 <= pos <= 1`) is the best position along the current slider, it is clamped to
+    /// that range extended by the [overshoot](SLSFramework::set_slider_overshoot).
+    /// Returns the parameters at `pos`, which were added to the data as the preferred point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider or `pos` is `NaN`, see
+    /// [try_proceed_optimization](SLSFramework::try_proceed_optimization).
+    pub fn proceed_optimization(&mut self, pos: f64) -> Vec<f64> {
+        let pos = self.clamp_position(pos);
+        match self.try_proceed_optimization(pos) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    // Records the answer like proceed_optimization, but keeps the slider, for DualObjectiveSLS
+    pub(crate) fn record_optimization(&mut self, pos: f64) -> Vec<f64> {
+        if !self.has_slider() {
+            panic!("{}", SLSError::NoSlider);
+        }
+        let pos = self.clamp_position(pos);
+        if pos.is_nan() {
+            panic!("{}", SLSError::PositionOutOfRange { pos });
+        }
+        unsafe {
+            let eigen_vec = cpp!([self as "SLSFramework*", pos as "double"] -> *const u8 as "const VectorXd *" {
+                return new VectorXd(self->recordOptimization(pos));
+            });
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            rsv
+        }
+    }
+
+    /// Take one step in the algorithm, failing if there is no current slider or `pos` is out of
+    /// range.
+    ///
+    /// See [proceed_optimization](SLSFramework::proceed_optimization). Instead of clamping `pos`,
+    /// this fails if `pos` is further out of range than the
+    /// [position tolerance](SLSFramework::set_position_tolerance).
+    pub fn try_proceed_optimization(&mut self, pos: f64) -> Result<Vec<f64>, SLSError> {
+        if !self.has_slider() {
+            return Err(SLSError::NoSlider);
+        }
+        let pos = self.snap_position(pos).ok_or(SLSError::PositionOutOfRange { pos })?;
+        unsafe {
+            let eigen_vec = cpp!(
+                [self as "SLSFramework*", pos as "double"]
+                  -> *const u8 as "const VectorXd *"
+            {
+                return new VectorXd(self->proceedOptimization(pos));
+            });
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            Ok(rsv)
+        }
+    }
+
+    /// Take one step in the algorithm, choosing the best of three points.
+    ///
+    /// The user is shown the positions `0`, `0.5` and `1` of the current slider, and `best`
+    /// (`< 3`) is the index of the preferred one among them. A single comparison is recorded,
+    /// with the preferred point over the other two, so each answer gives two preference
+    /// relations instead of one. Returns the preferred point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `best >= 3` or there is no current slider.
+    pub fn proceed_optimization_triple(&mut self, best: usize) -> Vec<f64> {
+        assert!(best < 3, "best must index one of the three positions");
+        if !self.has_slider() {
+            panic!("{}", SLSError::NoSlider);
+        }
+        unsafe {
+            let eigen_vec = cpp!(
+                [self as "SLSFramework*", best as "size_t"]
+                  -> *const u8 as "const VectorXd *"
+            {
+                return new VectorXd(self->proceedOptimizationTriple(best));
+            });
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            rsv
+        }
+    }
+
+    fn has_slider(&self) -> bool {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> bool as "bool" {
+                return self->slider != nullptr;
+            })
+        }
+    }
+
+    /// Leave the first slider to the caller.
+    ///
+    /// Normally a new framework starts with a random slider (see
+    /// [set_initial_strategy](SLSFramework::set_initial_strategy)). With `skip` set, there is no
+    /// slider until the caller provides one, both now if no step has been taken yet and after
+    /// every [reset](SLSFramework::reset). The call sequence is then:
+    ///
+    /// 1. `set_skip_initial_random(true)`
+    /// 2. [set_manual_slider](SLSFramework::set_manual_slider), or
+    ///    [set_initial_strategy](SLSFramework::set_initial_strategy) which creates the first
+    ///    slider according to the strategy
+    /// 3. [get_parameters_from_slider](SLSFramework::get_parameters_from_slider) and
+    ///    [proceed_optimization](SLSFramework::proceed_optimization) as usual
+    ///
+    /// Until step 2, methods that need a slider fail with [SLSError::NoSlider] or panic.
+    /// Turning `skip` off again before the first step creates the initial slider right away, if
+    /// none was provided.
+    pub fn set_skip_initial_random(&mut self, skip: bool) {
+        unsafe {
+            cpp!([self as "SLSFramework*", skip as "bool"] {
+                self->skip_initial_slider = skip;
+                if (self->x_max.rows() == 0 && (skip || self->slider == nullptr))
+                {
+                    self->updateSliderEnds();
+                }
+            });
+        }
+    }
+
+    #[cfg(test)]
+    fn clear_slider(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->slider = nullptr;
+            });
+        }
+    }
+
+    /// Start over without any data.
+    ///
+    /// All settings are kept, including whether hyperparameters are estimated.
+    pub fn reset(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->reset();
+            });
+        }
+    }
+
+    /// Start over without any data, but keep the current hyperparameters.
+    ///
+    /// Unlike [reset](SLSFramework::reset), the hyperparameters of the current regression (e.g.
+    /// the MAP estimates learned in this session) become fixed, and MAP estimation is disabled.
+    /// A new session on the same problem then starts with a model of the right scale. Call
+    /// [set_use_map_hyperparameters](SLSFramework::set_use_map_hyperparameters) to estimate them
+    /// again.
+    pub fn restart_keep_hyperparameters(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->restartKeepHyperparameters();
+            });
+        }
+    }
+
+    /// Replace the preference recorded by the most recent step.
+    ///
+    /// `pos` (`0 <= pos <= 1`) is the corrected position along the slider that step was taken on.
+    /// The regression and the next slider are recomputed as if that step had been taken with
+    /// `pos` in the first place.
+    ///
+    /// Only valid directly after [proceed_optimization](SLSFramework::proceed_optimization),
+    /// calling it repeatedly keeps amending the same step. `pos` is clamped like for
+    /// `proceed_optimization`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no step has been taken, if the data was edited since the most recent step, if
+    /// that step was taken with
+    /// [proceed_optimization_triple](SLSFramework::proceed_optimization_triple), or if `pos` is
+    /// `NaN`.
+    pub fn amend_last_comparison(&mut self, pos: f64) {
+        let valid = unsafe {
+            cpp!([self as "SLSFramework*"] -> bool as "bool" {
+                return self->previous_slider != nullptr;
+            })
+        };
+        assert!(valid, "amend_last_comparison called without a step to amend");
+        let triple = unsafe {
+            cpp!([self as "SLSFramework*"] -> bool as "bool" {
+                return self->step_triple;
+            })
+        };
+        assert!(!triple, "amend_last_comparison can't amend a step of proceed_optimization_triple");
+        let pos = self.clamp_position(pos);
+        if pos.is_nan() {
+            panic!("{}", SLSError::PositionOutOfRange { pos });
+        }
+        unsafe {
+            cpp!([self as "SLSFramework*", pos as "double"] {
+                self->amendLastComparison(pos);
+            });
+        }
+    }
+
+    /// Get positions along the current slider
+    ///
+    /// `pos` (`0 <= pos <= 1`) is the position along the slider, it is clamped to that range
+    /// extended by the [overshoot](SLSFramework::set_slider_overshoot)
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider or `pos` is `NaN`, see
+    /// [try_get_parameters_from_slider](SLSFramework::try_get_parameters_from_slider).
+    pub fn get_parameters_from_slider(&self, pos: f64) -> Vec<f64> {
+        let pos = self.clamp_position(pos);
+        match self.try_get_parameters_from_slider(pos) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Get positions along the current slider, failing if there is no current slider or `pos` is
+    /// out of range.
+    ///
+    /// See [get_parameters_from_slider](SLSFramework::get_parameters_from_slider). Instead of
+    /// clamping `pos`, this fails if `pos` is further out of range than the
+    /// [position tolerance](SLSFramework::set_position_tolerance).
+    pub fn try_get_parameters_from_slider(&self, pos: f64) -> Result<Vec<f64>, SLSError> {
+        if !self.has_slider() {
+            return Err(SLSError::NoSlider);
+        }
+        let pos = self.snap_position(pos).ok_or(SLSError::PositionOutOfRange { pos })?;
+        unsafe {
+            let eigen_vec = cpp!(
+                [self as "SLSFramework*", pos as "double"]
+                  -> *const u8 as "const VectorXd *"
+            {
+                VectorXd x = self->computeParametersFromSlider(pos);
+                return new VectorXd(x);
+            });
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            Ok(rsv)
+        }
+    }
+
+    /// Get the middle of the current slider
+    ///
+    /// Same as `get_parameters_from_slider(0.5)`, the recommended default position for the
+    /// slider handle.
+    pub fn slider_midpoint(&self) -> Vec<f64> {
+        self.get_parameters_from_slider(0.5)
+    }
 
-    struct SLSFramework {
-        std::shared_ptr<sequential_line_search::PreferenceRegressor> regressor;
-        std::shared_ptr<sequential_line_search::Slider> slider;
+    /// Get positions along the current slider for many positions at once
+    ///
+    /// Like [get_parameters_from_slider](SLSFramework::get_parameters_from_slider) for each of
+    /// `positions`, but in a single call into C++. Positions are clamped to `[0, 1]`, extended by
+    /// the [overshoot](SLSFramework::set_slider_overshoot).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider or one of `positions` is `NaN`.
+    pub fn get_parameters_from_slider_many(&self, positions: &[f64]) -> Vec<Vec<f64>> {
+        if !self.has_slider() {
+            panic!("{}", SLSError::NoSlider);
+        }
+        let positions: Vec<f64> = positions
+            .iter()
+            .map(|&pos| {
+                let clamped = self.clamp_position(pos);
+                if clamped.is_nan() {
+                    panic!("{}", SLSError::PositionOutOfRange { pos });
+                }
+                clamped
+            })
+            .collect();
+        let dim = self.dimension();
+        let n = positions.len();
+        let positions = positions.as_ptr();
 
-        sequential_line_search::Data data;
+        let mut res = vec![0.0f64; n * dim];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", positions as "const double*", n as "size_t", dim as "size_t", ptr as "double*"] {
+                for (size_t j = 0; j < n; ++j) {
+                    Map<VectorXd>(ptr + j * dim, dim) = self->computeParametersFromSlider(positions[j]);
+                }
+            });
+        }
+        res.chunks(dim).map(|c| c.to_vec()).collect()
+    }
 
-        size_t          dimension;
-        Eigen::VectorXd x_max;
-        double          y_max;
+    /// Get the change of the best score in the most recent step
+    ///
+    /// This is the latent score of the best point after the last
+    /// [proceed_optimization](SLSFramework::proceed_optimization) minus the one before it. Since
+    /// every step re-estimates all scores, it can be negative. `NaN` until two steps were taken.
+    pub fn last_improvement(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->y_max - self->previous_y_max;
+            })
+        }
+    }
 
-        SLSFramework(size_t d) :
-            dimension(d),
-            regressor(nullptr), slider(nullptr),
-            data(),
-            x_max(VectorXd::Zero(0)),
-            y_max(NAN)
-        {
-            computeRegression();
-            updateSliderEnds();
+    /// Estimate how many more steps it takes until the best score stops improving
+    ///
+    /// Fits an exponential decay `d_k = c * q^k` to the improvements `d_k` of the best score (see
+    /// [last_improvement](SLSFramework::last_improvement)) over the last five steps, by a least
+    /// squares line through `ln d_k`, and returns the number of steps until the fitted
+    /// improvement falls below `improvement_threshold` (`> 0`). `Some(0)` if the latest
+    /// improvement already is below it. `None` if fewer than three of those improvements are
+    /// positive, or if they don't decay. This is a rough heuristic, since every step re-estimates
+    /// all scores and the improvements are noisy.
+    pub fn estimated_remaining_steps(&self, improvement_threshold: f64) -> Option<usize> {
+        assert!(improvement_threshold > 0., "improvement threshold must be positive");
+        let history = self.best_history();
+        if history.len() < 2 {
+            return None;
+        }
+        let improvements: Vec<f64> = history.windows(2).map(|w| w[1] - w[0]).collect();
+        if improvements[improvements.len() - 1] < improvement_threshold {
+            return Some(0);
         }
 
-        void computeRegression()
-        {
-            regressor = std::make_shared<PreferenceRegressor>(data.X, data.D);
+        let recent = &improvements[improvements.len().saturating_sub(5)..];
+        let points: Vec<(f64, f64)> = recent
+            .iter()
+            .enumerate()
+            .filter(|&(_, &d)| d > 0.)
+            .map(|(k, &d)| (k as f64, d.ln()))
+            .collect();
+        if points.len() < 3 {
+            return None;
         }
 
-        void updateSliderEnds()
-        {
-            // If this is the first time...
-            if (x_max.rows() == 0)
-            {
-                slider = std::make_shared<Slider>(utils::generateRandomVector(dimension), utils::generateRandomVector(dimension), true);
-                return;
-            }
+        let n = points.len() as f64;
+        let mean_k = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_ln = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let slope = points.iter().map(|p| (p.0 - mean_k) * (p.1 - mean_ln)).sum::<f64>()
+            / points.iter().map(|p| (p.0 - mean_k) * (p.0 - mean_k)).sum::<f64>();
+        if slope.is_nan() || slope >= 0. {
+            return None;
+        }
 
-            const VectorXd x_1 = regressor->find_arg_max();
-            const VectorXd x_2 = acquisition_function::FindNextPoint(*regressor);
+        // ln d_k = mean_ln + slope * (k - mean_k) reaches ln(threshold) at k
+        let k = mean_k + (improvement_threshold.ln() - mean_ln) / slope;
+        let last = (recent.len() - 1) as f64;
+        Some((k - last).ceil().max(0.) as usize)
+    }
 
-            slider = std::make_shared<Slider>(x_1, x_2, true);
+    fn best_history(&self) -> Vec<f64> {
+        unsafe {
+            let len = cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->best_history.size();
+            });
+            let mut res = vec![0.0f64; len];
+            let ptr = res.as_mut_ptr();
+            cpp!([self as "SLSFramework*", len as "size_t", ptr as "double*"] {
+                for (size_t i = 0; i < len; ++i) {
+                    ptr[i] = self->best_history[i];
+                }
+            });
+            res
         }
+    }
 
-        const VectorXd computeParametersFromSlider(double value)
-        {
-            return slider->end_0 * (1.0 - value) + slider->end_1 *  value;
+    /// Get the best position to date
+    pub fn get_x_max(&self) -> Vec<f64> {
+        unsafe {
+            let eigen_vec = cpp!(
+                [self as "SLSFramework*"]
+                  -> *const u8 as "const VectorXd *"
+            {
+                return new VectorXd(self->x_max);
+            });
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            rsv
         }
+    }
 
-        void proceedOptimization(double slider_position)
-        {
-            // Add new preference data
-            const VectorXd x = computeParametersFromSlider(slider_position);
-            data.AddNewPoints(x, { slider->orig_0, slider->orig_1 });
-
-            // Compute regression
-            computeRegression();
-
-            // Check the current best
-            unsigned index;
-            y_max = regressor->y.maxCoeff(&index);
-            x_max = regressor->X.col(index);
+    /// Get the runner-up to the best position
+    ///
+    /// This is the sampled point with the second highest latent score. Among points with equal
+    /// scores the one sampled first ranks higher, like for [get_x_max](SLSFramework::get_x_max).
+    /// `None` with fewer than two points.
+    pub fn get_x_second(&self) -> Option<Vec<f64>> {
+        unsafe {
+            let eigen_vec = cpp!(
+                [self as "SLSFramework*"]
+                  -> *const u8 as "const VectorXd *"
+            {
+                const VectorXd& y = self->getRegressor().y;
+                if (y.size() < 2) return nullptr;
 
-            // Update slider ends
-            updateSliderEnds();
+                Index best, second = -1;
+                y.maxCoeff(&best);
+                for (Index i = 0; i < y.size(); ++i) {
+                    if (i != best && (second < 0 || y(i) > y(second))) second = i;
+                }
+                return new VectorXd(self->getRegressor().X.col(second));
+            });
+            if eigen_vec.is_null() {
+                return None;
+            }
+            let rsv = as_rust_vec(eigen_vec);
+            cpp!([eigen_vec as "const VectorXd *"] {
+                delete eigen_vec;
+            });
+            Some(rsv)
         }
+    }
 
-    };
-}}
+    /// Get a pointer to the best position to date, and its length
+    ///
+    /// Reads the data of [get_x_max](SLSFramework::get_x_max) in place, without copying it.
+    ///
+    /// # Safety
+    ///
+    /// The pointer is only valid until the framework is mutated: any method taking `&mut self`
+    /// (or dropping the framework) may reallocate or free the data. It must not be written to.
+    pub unsafe fn x_max_ptr(&self) -> (*const f64, usize) {
+        let len = cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+            return self->x_max.rows();
+        });
+        let ptr = cpp!([self as "SLSFramework*"] -> *const f64 as "const double*" {
+            return self->x_max.data();
+        });
+        (ptr, len)
+    }
 
-cpp_class!(
-    /// State of the sequential line search algorithm
-    pub unsafe struct SLSFramework as "SLSFramework"
-);
+    fn check_dimension(&self, x: &[f64]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        if x.len() != dim {
+            return Err(SLSError::DimensionMismatch { expected: dim, found: x.len() });
+        }
+        Ok(())
+    }
 
+    fn flatten_points(&self, points: &[Vec<f64>]) -> Vec<f64> {
+        let dim = self.dimension();
+        for p in points {
+            assert_eq!(p.len(), dim, "point has the wrong number of dimensions");
+        }
+        points.iter().flatten().cloned().collect()
+    }
 
-unsafe fn as_rust_vec(ev: *const u8) -> Vec<f64> {
-    let dim = cpp!([ev as "const VectorXd*"] -> usize as "size_t" {
-            return ev->rows();
-        });
+    fn dimension(&self) -> usize {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->dimension;
+            })
+        }
+    }
 
-    let mut res = vec![0.0f64; dim];
-    let ptr = res.as_mut_ptr();
-    cpp!([ev as "const VectorXd*", dim as "size_t", ptr as "double*"] {
-        for (size_t i = 0; i < dim; ++i) {
-            ptr[i] = (*ev)(i);
+    fn num_points(&self) -> usize {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->data.X.cols();
+            })
         }
-    });
-    res
-}
+    }
 
-impl SLSFramework {
-    /// Initializes the algorithm.
+    /// Get all points sampled so far
     ///
-    /// `dim` is the number of dimensions of the parameter space
-    pub fn new(dim: usize) -> Self {
+    /// Each point is a column of the underlying data matrix, in the order they were added.
+    pub fn get_all_points(&self) -> Vec<Vec<f64>> {
         unsafe {
-        cpp!([dim as "size_t"] -> SLSFramework as "SLSFramework" {
-            return SLSFramework(dim);
-        })
+            let eigen_mat = cpp!(
+                [self as "SLSFramework*"]
+                  -> *const u8 as "const MatrixXd *"
+            {
+                return &self->data.X;
+            });
+            as_rust_matrix(eigen_mat)
         }
     }
 
-    /// Take one step in the algorithm.
+    /// Get all recorded preferences
     ///
-    /// `pos` (`0 <= pos <= 1`) is the best position along the current slider
-    pub fn proceed_optimization(&mut self, pos: f64) {
+    /// Each entry is `(preferred, rejected)`: the point at index `preferred` was chosen over
+    /// every point at the indices in `rejected`. Indices refer to positions in
+    /// [get_all_points](SLSFramework::get_all_points). A single step usually records the chosen
+    /// point as preferred over both slider origins, but close points are merged, so neither the
+    /// number of entries per step nor the number of rejected indices is fixed.
+    pub fn get_comparisons(&self) -> Vec<(usize, Vec<usize>)> {
         unsafe {
-            cpp!([self as "SLSFramework*", pos as "double"] {
-                self->proceedOptimization(pos);
+            let n = cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->data.D.size();
             });
+            (0..n)
+                .map(|i| {
+                    let len = cpp!([self as "SLSFramework*", i as "size_t"] -> usize as "size_t" {
+                        return self->data.D[i].size();
+                    });
+                    let mut indices = vec![0usize; len];
+                    let ptr = indices.as_mut_ptr();
+                    cpp!([self as "SLSFramework*", i as "size_t", len as "size_t", ptr as "size_t*"] {
+                        for (size_t k = 0; k < len; ++k) {
+                            ptr[k] = self->data.D[i][k];
+                        }
+                    });
+                    (indices[0], indices[1..].to_vec())
+                })
+                .collect()
         }
     }
 
-    /// Get positions along the current slider
+    /// Get where the points of the most recent step are stored
     ///
-    /// `pos` (`0 <= pos <= 1`) is the position along the slider
-    pub fn get_parameters_from_slider(&self, pos: f64) -> Vec<f64> {
+    /// Returns `(chosen, origins)`: the index of the chosen point and the indices of the points
+    /// it was preferred over (the slider origins), as indices into
+    /// [get_all_points](SLSFramework::get_all_points). Points close to an existing sample are
+    /// merged with it, so an index can refer to an older point. Indices stay valid until points
+    /// are removed or replaced. `None` if there was no step since then, or since the framework
+    /// was created or [reset](SLSFramework::reset).
+    pub fn last_added_indices(&self) -> Option<(usize, Vec<usize>)> {
         unsafe {
-            let eigen_vec = cpp!(
-                [self as "SLSFramework*", pos as "double"]
-                  -> *const u8 as "const VectorXd *"
-            {
-                VectorXd x = self->computeParametersFromSlider(pos);
-                return new VectorXd(x);
+            let len = cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->step_indices.size();
             });
-            let rsv = as_rust_vec(eigen_vec);
-            cpp!([eigen_vec as "const VectorXd *"] {
-                delete eigen_vec;
+            if len == 0 {
+                return None;
+            }
+            let mut indices = vec![0usize; len];
+            let ptr = indices.as_mut_ptr();
+            cpp!([self as "SLSFramework*", len as "size_t", ptr as "size_t*"] {
+                for (size_t k = 0; k < len; ++k) {
+                    ptr[k] = self->step_indices[k];
+                }
             });
-            rsv
+            Some((indices[0], indices[1..].to_vec()))
         }
     }
 
-    /// Get the best position to date
-    pub fn get_x_max(&self) -> Vec<f64> {
+    /// Get the Euclidean distance from `x` to the closest sampled point
+    ///
+    /// Infinite if there are no points yet.
+    pub fn distance_to_nearest_sample(&self, x: &[f64]) -> f64 {
+        let dim = self.dimension();
+        assert_eq!(x.len(), dim, "point has the wrong number of dimensions");
+        let x = x.as_ptr();
         unsafe {
-            let eigen_vec = cpp!(
-                [self as "SLSFramework*"]
-                  -> *const u8 as "const VectorXd *"
-            {
-                return &self->x_max;
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t"] -> f64 as "double" {
+                const Map<const VectorXd> v(x, dim);
+                double distance = INFINITY;
+                for (int i = 0; i < self->data.X.cols(); ++i) {
+                    distance = std::min(distance, (self->data.X.col(i) - v).norm());
+                }
+                return distance;
+            })
+        }
+    }
+
+    /// Get the variance of the sampled points in each dimension
+    ///
+    /// This is the sample variance of each coordinate over
+    /// [all points](SLSFramework::get_all_points). A dimension with a variance close to zero was
+    /// hardly varied by the sliders shown so far, so the session says little about it. All zero
+    /// with fewer than two points.
+    pub fn explored_variance(&self) -> Vec<f64> {
+        let points = self.get_all_points();
+        let n = points.len();
+        if n < 2 {
+            return vec![0.; self.dimension()];
+        }
+        (0..self.dimension())
+            .map(|d| {
+                let mean = points.iter().map(|p| p[d]).sum::<f64>() / n as f64;
+                points.iter().map(|p| (p[d] - mean) * (p[d] - mean)).sum::<f64>() / (n - 1) as f64
+            })
+            .collect()
+    }
+
+    /// Count the comparisons each sampled point takes part in
+    ///
+    /// Entry `i` is the number of [comparisons](SLSFramework::get_comparisons) referring to point
+    /// `i`, as preferred or rejected. Points in few comparisons contribute little to the model
+    /// and are candidates for [remove_point](SLSFramework::remove_point).
+    pub fn point_usage_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.num_points()];
+        for (preferred, rejected) in self.get_comparisons() {
+            counts[preferred] += 1;
+            for i in rejected {
+                counts[i] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Add an absolute rating of a point
+    ///
+    /// The preference model only learns from comparisons, so ratings are approximated by them:
+    /// `x` is recorded as preferred over every previously rated point with a lower `score` and as
+    /// rejected for every one with a higher score, equal scores add nothing. Only the order of
+    /// the scores matters, not their differences, and the first rating adds no data until there
+    /// is a second one. Ratings are kept until [reset](SLSFramework::reset) or
+    /// [set_dataset](SLSFramework::set_dataset) and the regression is recomputed afterwards.
+    /// The current slider is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` has the wrong dimension.
+    pub fn add_absolute_observation(&mut self, x: &[f64], score: f64) {
+        let dim = self.dimension();
+        assert_eq!(x.len(), dim, "point has the wrong number of dimensions");
+        let x = x.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t", score as "double"] {
+                self->addAbsoluteObservation(Map<const VectorXd>(x, dim), score);
+            });
+        }
+    }
+
+    /// Remove a sampled point from the data
+    ///
+    /// Comparisons in which the point was preferred are dropped, otherwise it is removed from the
+    /// rejected points. Indices of all later points shift down by one. The regression is
+    /// recomputed afterwards.
+    pub fn remove_point(&mut self, index: usize) -> Result<(), SLSError> {
+        let len = self.num_points();
+        if index >= len {
+            return Err(SLSError::IndexOutOfRange { index, len });
+        }
+        unsafe {
+            cpp!([self as "SLSFramework*", index as "size_t"] {
+                self->removePoint(index);
             });
-            as_rust_vec(eigen_vec)
         }
+        Ok(())
     }
 }
 
@@ -219,3 +2187,295 @@ fn test_point() {
     dbg!(sls.get_x_max());
 }
 
+
+#[test]
+fn test_zero_length_slider() {
+    let p = vec![0.5f64; 3];
+    let snap = StateSnapshot {
+        dimension: 3,
+        points: vec![],
+        comparisons: vec![],
+        slider_origins: (p.clone(), p.clone()),
+        slider_ends: (p.clone(), p),
+        iterations: 0,
+    };
+    let sls = SLSFramework::from_snapshot(&snap).unwrap();
+    assert!(sls.slider_length() > 0.);
+
+    let p = vec![0.5f64; 3];
+    let mut sls = SLSFramework::new(3);
+    sls.set_warmup_steps(0);
+    sls.set_fixed_anchor(&p).unwrap();
+    sls.set_next_candidate(&p).unwrap();
+    sls.proceed_optimization(0.3);
+    let slider = sls.current_slider();
+    assert_eq!(slider.end_0(), &p[..]);
+    assert_ne!(slider.end_1(), &p[..]);
+    assert_eq!(sls.health_warning(), Some(HealthWarning::ZeroLengthSlider));
+}
+
+#[test]
+fn test_drop() {
+    let before = live_frameworks();
+    for _ in 0..1000 {
+        let sls = SLSFramework::new(3);
+        let _copy = sls.clone();
+    }
+    for _ in 0..10 {
+        let mut sls = SLSFramework::new(3);
+        sls.proceed_optimization(0.5);
+        let mut copy = sls.clone();
+        drop(sls);
+        copy.proceed_optimization(0.5);
+    }
+    assert_eq!(live_frameworks(), before);
+}
+
+#[test]
+fn test_x_max_between_steps() {
+    let mut sls = SLSFramework::new(2);
+    assert!(sls.get_x_max().is_empty());
+    for _ in 0..5 {
+        sls.proceed_optimization(0.3);
+        let x_max = sls.get_x_max();
+        assert_eq!(x_max.len(), 2);
+        assert_eq!(sls.get_x_max(), x_max);
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least one dimension")]
+fn test_zero_dimensions() {
+    SLSFramework::new(0);
+}
+
+#[test]
+fn test_proceed_without_slider() {
+    let mut sls = SLSFramework::new(2);
+    sls.clear_slider();
+    assert_eq!(sls.try_proceed_optimization(0.5), Err(SLSError::NoSlider));
+}
+
+#[test]
+fn test_new_with_capacity() {
+    let start = (vec![0.2, 0.9, 0.4], vec![0.7, 0.1, 0.6]);
+    let mut a = SLSFramework::new(3);
+    let mut b = SLSFramework::new_with_capacity(3, 20);
+    make_deterministic(&mut a, &start);
+    make_deterministic(&mut b, &start);
+    assert_eq!((a.get_parameters_from_slider(0.), a.get_parameters_from_slider(1.)), start);
+    for &pos in &[0.3, 0.8, 0.5, 0.1] {
+        assert_eq!(a.proceed_optimization(pos), b.proceed_optimization(pos));
+    }
+    assert_eq!(a.get_x_max(), b.get_x_max());
+    assert_eq!(a.get_all_points(), b.get_all_points());
+}
+
+#[test]
+fn test_new_with_rng_parallel() {
+    fn session(seed: u64) -> Vec<Vec<f64>> {
+        let mut sls = SLSFramework::new_with_rng(2, seed);
+        sls.set_deterministic_acquisition(true);
+        sls.set_warmup_steps(3);
+        sls.set_endpoint_jitter(0.05);
+        for &pos in &[0.3, 0.8, 0.5, 0.1, 0.6] {
+            sls.proceed_optimization(pos);
+        }
+        sls.get_all_points()
+    }
+
+    let seeds: Vec<u64> = (0..32).collect();
+    let handles: Vec<_> = seeds.iter().map(|&seed| std::thread::spawn(move || session(seed))).collect();
+    let parallel: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    for (&seed, points) in seeds.iter().zip(&parallel) {
+        assert_eq!(&session(seed), points);
+    }
+    assert_ne!(parallel[0], parallel[1]);
+}
+
+#[test]
+fn test_acquisition_schedule() {
+    let start = (vec![0.2, 0.9], vec![0.7, 0.1]);
+    let mut scheduled = SLSFramework::new(2);
+    let mut constant = SLSFramework::new(2);
+    make_deterministic(&mut scheduled, &start);
+    make_deterministic(&mut constant, &start);
+    scheduled.set_acquisition_schedule(Schedule::Linear { start: 0.05, end: 0., over: 3 });
+    constant.set_acquisition_param(0.05);
+
+    scheduled.proceed_optimization(0.3);
+    constant.proceed_optimization(0.3);
+    let ends = |sls: &SLSFramework| (sls.get_parameters_from_slider(0.), sls.get_parameters_from_slider(1.));
+    assert_eq!(ends(&scheduled), ends(&constant));
+    let x = [0.4, 0.6];
+    assert_eq!(scheduled.acquisition_value(&x), constant.acquisition_value(&x));
+}
+
+#[test]
+fn test_out_of_range_positions() {
+    let mut sls = SLSFramework::new(2);
+    let (end_0, end_1) = (sls.get_parameters_from_slider(0.), sls.get_parameters_from_slider(1.));
+    assert_eq!(sls.get_parameters_from_slider(1.0000001), end_1);
+    assert_eq!(sls.get_parameters_from_slider(1.5), end_1);
+    assert_eq!(sls.get_parameters_from_slider(-0.2), end_0);
+    assert_eq!(sls.try_get_parameters_from_slider(1.0000001), Ok(end_1.clone()));
+    assert_eq!(sls.try_get_parameters_from_slider(1.5), Err(SLSError::PositionOutOfRange { pos: 1.5 }));
+    assert_eq!(sls.try_get_parameters_from_slider(-0.2), Err(SLSError::PositionOutOfRange { pos: -0.2 }));
+    assert_eq!(sls.get_parameters_from_slider_many(&[1.0000001, -0.2]), vec![end_1.clone(), end_0.clone()]);
+
+    assert_eq!(sls.try_proceed_optimization(1.5), Err(SLSError::PositionOutOfRange { pos: 1.5 }));
+    assert_eq!(sls.try_proceed_optimization(-0.2), Err(SLSError::PositionOutOfRange { pos: -0.2 }));
+    assert_eq!(sls.num_points(), 0);
+    assert_eq!(sls.proceed_optimization(1.5), end_1);
+    let end_0 = sls.get_parameters_from_slider(0.);
+    assert_eq!(sls.proceed_optimization(-0.2), end_0);
+    let end_1 = sls.get_parameters_from_slider(1.);
+    assert_eq!(sls.try_proceed_optimization(1.0000001), Ok(end_1));
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_nan_position() {
+    SLSFramework::new(2).proceed_optimization(f64::NAN);
+}
+
+#[test]
+fn test_set_dataset() {
+    let mut sls = SLSFramework::new(2);
+    for &pos in &[0.2, 0.7, 0.4] {
+        sls.proceed_optimization(pos);
+    }
+    sls.add_absolute_observation(&[0.5, 0.5], 1.);
+    let before = (sls.get_all_points(), sls.get_comparisons());
+
+    let points = vec![vec![0.1, 0.2], vec![0.8, 0.3], vec![0.4, 0.9]];
+    let invalid = vec![(0, vec![1]), (1, vec![])];
+    assert_eq!(sls.set_dataset(&points, &invalid), Err(SLSError::InvalidComparison { index: 1 }));
+    let invalid = vec![(1, vec![1, 2])];
+    assert_eq!(sls.set_dataset(&points, &invalid), Err(SLSError::InvalidComparison { index: 0 }));
+    let invalid = vec![(0, vec![3])];
+    assert_eq!(sls.set_dataset(&points, &invalid), Err(SLSError::IndexOutOfRange { index: 3, len: 3 }));
+    assert_eq!((sls.get_all_points(), sls.get_comparisons()), before);
+
+    let comparisons = vec![(1, vec![0]), (1, vec![2]), (2, vec![0])];
+    sls.set_dataset(&points, &comparisons).unwrap();
+    assert_eq!(sls.get_all_points(), points);
+    assert_eq!(sls.get_comparisons(), comparisons);
+    assert_eq!(sls.get_x_max(), points[1]);
+    assert!(sls.has_slider());
+
+    // The steps and ratings before belong to the replaced data
+    assert!(sls.last_improvement().is_nan());
+    assert_eq!(sls.last_added_indices(), None);
+    assert_eq!(sls.repeat_comparison(0), Err(SLSError::StepOutOfRange { index: 0, len: 0 }));
+    sls.add_absolute_observation(&[0.6, 0.6], 0.);
+    assert_eq!(sls.get_comparisons(), comparisons);
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let mut sls = SLSFramework::new(2);
+    for &pos in &[0.2, 0.7, 0.4] {
+        sls.proceed_optimization(pos);
+    }
+    let restored = SLSFramework::from_snapshot(&sls.snapshot()).unwrap();
+    assert_eq!(restored.get_all_points(), sls.get_all_points());
+    assert_eq!(restored.get_comparisons(), sls.get_comparisons());
+    assert_eq!(restored.current_slider(), sls.current_slider());
+
+    // Not enlarged, the ends are the origins
+    sls.set_manual_slider(&[0.1, 0.3], &[0.6, 0.9]).unwrap();
+    let restored = SLSFramework::from_snapshot(&sls.snapshot()).unwrap();
+    assert_eq!(restored.current_slider(), sls.current_slider());
+    assert_eq!(restored.current_slider().end_0(), &[0.1, 0.3]);
+}
+
+#[test]
+fn test_remove_point() {
+    let mut sls = SLSFramework::new(2);
+    let points = vec![vec![0.1, 0.2], vec![0.8, 0.3], vec![0.4, 0.9], vec![0.6, 0.5]];
+    sls.set_dataset(&points, &[(1, vec![0, 2]), (2, vec![0]), (3, vec![1])]).unwrap();
+
+    // Preferred once and rejected once, later indices shift down
+    sls.remove_point(2).unwrap();
+    assert_eq!(sls.get_all_points(), vec![points[0].clone(), points[1].clone(), points[3].clone()]);
+    assert_eq!(sls.get_comparisons(), vec![(1, vec![0]), (2, vec![1])]);
+    assert_eq!(sls.point_usage_counts(), vec![1, 2, 1]);
+
+    // Dropping its preference, and the comparison left without rejected points
+    sls.remove_point(1).unwrap();
+    assert_eq!(sls.get_all_points(), vec![points[0].clone(), points[3].clone()]);
+    assert_eq!(sls.get_comparisons(), vec![]);
+    assert_eq!(sls.point_usage_counts(), vec![0, 0]);
+
+    sls.remove_point(0).unwrap();
+    sls.remove_point(0).unwrap();
+    assert!(sls.get_all_points().is_empty());
+    assert_eq!(sls.point_usage_counts(), vec![]);
+    assert!(sls.get_x_max().is_empty());
+    assert_eq!(sls.remove_point(0), Err(SLSError::IndexOutOfRange { index: 0, len: 0 }));
+}
+
+#[test]
+fn test_amend_last_comparison() {
+    let start = (vec![0.2, 0.9], vec![0.7, 0.1]);
+    let mut a = SLSFramework::new(2);
+    let mut b = SLSFramework::new(2);
+    for sls in [&mut a, &mut b] {
+        make_deterministic(sls, &start);
+        sls.proceed_optimization(0.4);
+    }
+
+    a.proceed_optimization(0.3);
+    a.amend_last_comparison(0.1);
+    a.amend_last_comparison(0.8);
+    b.proceed_optimization(0.8);
+    assert_eq!(a.get_all_points(), b.get_all_points());
+    assert_eq!(a.get_comparisons(), b.get_comparisons());
+    assert_eq!(a.get_x_max(), b.get_x_max());
+    assert_eq!(a.current_slider(), b.current_slider());
+}
+
+#[test]
+fn test_information_gain() {
+    // Samples in one corner say almost nothing about the rest of the cube
+    let mut sls = SLSFramework::new(8);
+    assert_eq!(sls.information_gain(), 0.);
+    let points = vec![vec![0.; 8], vec![0.02; 8]];
+    sls.set_dataset(&points, &[(1, vec![0])]).unwrap();
+    let gain = sls.information_gain();
+    assert!((0. ..0.05).contains(&gain), "gain {} far from the samples", gain);
+
+    let mut sls = SLSFramework::new(2);
+    for &pos in &[0.2, 0.7, 0.4, 0.9, 0.5] {
+        sls.proceed_optimization(pos);
+        let gain = sls.information_gain();
+        assert!((0. ..1.).contains(&gain), "gain {} out of range", gain);
+    }
+}
+
+#[test]
+fn test_model_fingerprint() {
+    let mut sls = SLSFramework::new(2);
+    let empty = sls.model_fingerprint();
+    assert_eq!(SLSFramework::from_snapshot(&sls.snapshot()).unwrap().model_fingerprint(), empty);
+
+    sls.proceed_optimization(0.3);
+    let one_step = sls.model_fingerprint();
+    assert_ne!(one_step, empty);
+    sls.proceed_optimization(0.7);
+    assert_ne!(sls.model_fingerprint(), one_step);
+    let restored = SLSFramework::from_snapshot(&sls.snapshot()).unwrap();
+    assert_eq!(restored.model_fingerprint(), sls.model_fingerprint());
+
+    while sls.num_points() > 0 {
+        sls.remove_point(0).unwrap();
+    }
+    assert_eq!(sls.model_fingerprint(), empty);
+}
+
+#[test]
+fn test_try_new() {
+    assert_eq!(SLSFramework::try_new(0).err(), Some(SLSError::ZeroDimensions));
+    assert!(SLSFramework::try_new(2).is_ok());
+}
@@ -0,0 +1,490 @@
+use crate::{as_rust_matrix, SLSError, SLSFramework};
+
+impl SLSFramework {
+    /// Draw a sample of the posterior of the preference function
+    ///
+    /// Returns the value of one function drawn from the Gaussian process posterior at each of
+    /// `points`. The sample is joint, so values at nearby points are correlated, unlike
+    /// independent draws from the marginal at each point. The same `seed` gives the same sample.
+    pub fn sample_posterior(&self, points: &[Vec<f64>], seed: u64) -> Vec<f64> {
+        let n = points.len();
+        let dim = self.dimension();
+        let flat = self.flatten_points(points);
+        let points = flat.as_ptr();
+
+        let mut res = vec![0.0f64; n];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", points as "const double*", dim as "size_t", n as "size_t", seed as "uint64_t", ptr as "double*"] {
+                const Map<const MatrixXd> X(points, dim, n);
+
+                // Small jitter keeps the factorization stable for nearly identical points
+                const MatrixXd covariance = self->posteriorCovariance(X) + 1e-10 * MatrixXd::Identity(n, n);
+                const MatrixXd L = covariance.llt().matrixL();
+
+                std::mt19937_64 rng(seed);
+                std::normal_distribution<double> normal;
+                VectorXd z(n);
+                for (size_t i = 0; i < n; ++i) z(i) = normal(rng);
+
+                const VectorXd f = L * z;
+                for (size_t i = 0; i < n; ++i) {
+                    ptr[i] = self->getRegressor().estimate_y(X.col(i)) + f(i);
+                }
+            });
+        }
+        res
+    }
+
+    /// Get the posterior mean at both ends of the current slider
+    ///
+    /// The end with the higher score is a reasonable default position for the slider handle.
+    /// These are predictions of the model, not preferences of the user, and are unreliable early
+    /// in a session.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider.
+    pub fn slider_end_scores(&self) -> (f64, f64) {
+        if !self.has_slider() {
+            panic!("{}", SLSError::NoSlider);
+        }
+        let mut res = [0.0f64; 2];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", ptr as "double*"] {
+                ptr[0] = self->getRegressor().estimate_y(self->slider->end_0);
+                ptr[1] = self->getRegressor().estimate_y(self->slider->end_1);
+            });
+        }
+        (res[0], res[1])
+    }
+
+    /// Predict the latent preference at `x`
+    ///
+    /// Returns the mean and variance of the Gaussian process posterior at `x`.
+    pub fn predict(&self, x: &[f64]) -> (f64, f64) {
+        let dim = self.dimension();
+        assert_eq!(x.len(), dim, "point has the wrong number of dimensions");
+        let x = x.as_ptr();
+
+        let mut res = [0.0f64; 2];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t", ptr as "double*"] {
+                const Map<const VectorXd> v(x, dim);
+                const double s = self->getRegressor().estimate_s(v);
+                ptr[0] = self->getRegressor().estimate_y(v);
+                ptr[1] = s * s;
+            });
+        }
+        (res[0], res[1])
+    }
+
+    /// Split the uncertainty at `x` into the model variance and the noise variance
+    ///
+    /// Returns `(epistemic, noise)`, which add up to the variance [predict](SLSFramework::predict)
+    /// returns. The epistemic part is the posterior variance of the latent preference, it
+    /// shrinks with more comparisons near `x`. The noise part is the variance the regression
+    /// assumes on each latent score, the [regularization](SLSFramework::get_regularization), and
+    /// does not depend on the data.
+    pub fn predict_variance_components(&self, x: &[f64]) -> (f64, f64) {
+        let (_, variance) = self.predict(x);
+        let noise = self.get_regularization();
+        (variance - noise, noise)
+    }
+
+    /// Predict an interval for the latent preference at `x`
+    ///
+    /// Returns `(mean - z*std, mean + z*std)` from [predict](SLSFramework::predict). The
+    /// posterior is Gaussian, so `z = 1.96` gives a 95% interval. Scores have no natural range,
+    /// so the interval is never clamped.
+    pub fn predict_interval(&self, x: &[f64], z: f64) -> (f64, f64) {
+        let (mean, variance) = self.predict(x);
+        let std = variance.sqrt();
+        (mean - z * std, mean + z * std)
+    }
+
+    /// Evaluate the acquisition function at `x`
+    ///
+    /// This is the expected improvement over the current best that sliders are chosen by, see
+    /// [set_acquisition_param](SLSFramework::set_acquisition_param). `NaN` if there is no data.
+    pub fn acquisition_value(&self, x: &[f64]) -> f64 {
+        let dim = self.dimension();
+        assert_eq!(x.len(), dim, "point has the wrong number of dimensions");
+        let x = x.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t"] -> f64 as "double" {
+                if (self->data.X.cols() == 0) return NAN;
+                return self->acquisitionValue(Map<const VectorXd>(x, dim));
+            })
+        }
+    }
+
+    /// Get the gradient of [acquisition_value](SLSFramework::acquisition_value) at `x`
+    ///
+    /// Entry `i` is the derivative with respect to `x[i]`, in closed form from the squared
+    /// exponential kernel and the expected improvement, for optimizing the acquisition function
+    /// by gradient ascent. Like `acquisition_value`, this ignores
+    /// [set_custom_acquisition](SLSFramework::set_custom_acquisition). All zero if there is no
+    /// data.
+    pub fn acquisition_gradient(&self, x: &[f64]) -> Vec<f64> {
+        let dim = self.dimension();
+        assert_eq!(x.len(), dim, "point has the wrong number of dimensions");
+        let mut gradient = vec![0.; dim];
+        let x = x.as_ptr();
+        let ptr = gradient.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t", ptr as "double*"] {
+                if (self->data.X.cols() == 0) return;
+                Map<VectorXd>(ptr, dim) = self->acquisitionGradient(Map<const VectorXd>(x, dim));
+            });
+        }
+        gradient
+    }
+
+    /// Evaluate the acquisition function on a regular grid over a box
+    ///
+    /// The box spans from `lower` to `upper` with `per_dim` (`>= 2`) points per dimension,
+    /// including both bounds. Returns the value of
+    /// [acquisition_value](SLSFramework::acquisition_value) at each grid point together with the
+    /// grid points, where the first dimension varies fastest. The grid has `per_dim^dim` points,
+    /// so this is only practical in a few dimensions. The values are `NaN` if there is no data.
+    pub fn acquisition_on_grid(
+        &self,
+        lower: &[f64],
+        upper: &[f64],
+        per_dim: usize,
+    ) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let dim = self.dimension();
+        assert_eq!(lower.len(), dim, "point has the wrong number of dimensions");
+        assert_eq!(upper.len(), dim, "point has the wrong number of dimensions");
+        assert!(per_dim >= 2, "the grid needs at least two points per dimension");
+
+        let total = (0..dim).fold(1usize, |n, _| n * per_dim);
+        let lower = lower.as_ptr();
+        let upper = upper.as_ptr();
+        let mut values = vec![0.0f64; total];
+        let mut points = vec![0.0f64; total * dim];
+        let values_ptr = values.as_mut_ptr();
+        let points_ptr = points.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", lower as "const double*", upper as "const double*", dim as "size_t", per_dim as "size_t", total as "size_t", values_ptr as "double*", points_ptr as "double*"] {
+                const bool has_data = self->data.X.cols() > 0;
+                for (size_t i = 0; i < total; ++i) {
+                    Map<VectorXd> x(points_ptr + i * dim, dim);
+                    size_t rest = i;
+                    for (size_t d = 0; d < dim; ++d) {
+                        const double t = double(rest % per_dim) / (per_dim - 1);
+                        x(d) = lower[d] * (1.0 - t) + upper[d] * t;
+                        rest /= per_dim;
+                    }
+                    values_ptr[i] = has_data ? self->acquisitionValue(x) : NAN;
+                }
+            });
+        }
+        (values, points.chunks(dim).map(|c| c.to_vec()).collect())
+    }
+
+    /// Evaluate the acquisition function at the points the current slider was built from
+    ///
+    /// Returns the values at the current best and at the point chosen by the acquisition
+    /// function, before any nudging or jitter moved it. The second value tells how promising the
+    /// current comparison is expected to be, and is `NaN` for sliders that were set by hand. Both
+    /// are `NaN` if there is no data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no slider.
+    pub fn current_slider_acquisition(&self) -> (f64, f64) {
+        let slider = self.current_slider();
+        let best = self.acquisition_value(slider.origins().0);
+        let chosen = unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                if (self->data.X.cols() == 0 || self->slider_acquisition_point.rows() == 0) return NAN;
+                return self->acquisitionValue(self->slider_acquisition_point);
+            })
+        };
+        (best, chosen)
+    }
+
+    /// Evaluate the posterior mean along the segment from `a` to `b`
+    ///
+    /// Returns the mean at `resolution` evenly spaced points, including both ends. This does not
+    /// depend on the current slider.
+    pub fn posterior_mean_along(&self, a: &[f64], b: &[f64], resolution: usize) -> Vec<f64> {
+        let dim = self.dimension();
+        assert_eq!(a.len(), dim, "point has the wrong number of dimensions");
+        assert_eq!(b.len(), dim, "point has the wrong number of dimensions");
+        let a = a.as_ptr();
+        let b = b.as_ptr();
+
+        let mut res = vec![0.0f64; resolution];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", a as "const double*", b as "const double*", dim as "size_t", resolution as "size_t", ptr as "double*"] {
+                const Map<const VectorXd> x_a(a, dim);
+                const Map<const VectorXd> x_b(b, dim);
+                for (size_t i = 0; i < resolution; ++i) {
+                    const double t = resolution > 1 ? double(i) / (resolution - 1) : 0.0;
+                    ptr[i] = self->getRegressor().estimate_y(x_a * (1.0 - t) + x_b * t);
+                }
+            });
+        }
+        res
+    }
+
+    /// Count the local maxima of the posterior mean
+    ///
+    /// The mean is evaluated on a regular grid with `resolution` (`>= 2`) points per dimension
+    /// over the unit cube, i.e. `resolution^dim` points. A grid point counts as a mode if no
+    /// neighbor along any axis is higher and its mean is at least halfway between the lowest and
+    /// the highest value on the grid, which ignores small bumps. This is a heuristic: too coarse
+    /// a grid merges or misses modes, and a flat posterior counts as a single mode.
+    pub fn num_posterior_modes(&self, resolution: usize) -> usize {
+        assert!(resolution >= 2, "the grid needs at least two points per dimension");
+        let dim = self.dimension();
+        unsafe {
+            cpp!([self as "SLSFramework*", resolution as "size_t", dim as "size_t"] -> usize as "size_t" {
+                size_t total = 1;
+                for (size_t d = 0; d < dim; ++d) total *= resolution;
+
+                // Grid point i has coordinate (i / resolution^d) % resolution in dimension d
+                std::vector<double> values(total);
+                VectorXd x(dim);
+                for (size_t i = 0; i < total; ++i) {
+                    size_t rest = i;
+                    for (size_t d = 0; d < dim; ++d) {
+                        x(d) = double(rest % resolution) / (resolution - 1);
+                        rest /= resolution;
+                    }
+                    values[i] = self->getRegressor().estimate_y(x);
+                }
+
+                const double min = *std::min_element(values.begin(), values.end());
+                const double max = *std::max_element(values.begin(), values.end());
+                const double threshold = min + 0.5 * (max - min);
+
+                size_t modes = 0;
+                for (size_t i = 0; i < total; ++i) {
+                    if (values[i] < threshold) continue;
+
+                    // Ties go to the lower neighbor, so plateaus count once
+                    bool is_max = true;
+                    size_t stride = 1;
+                    for (size_t d = 0; d < dim && is_max; ++d) {
+                        const size_t coordinate = (i / stride) % resolution;
+                        if (coordinate > 0 && values[i - stride] >= values[i]) is_max = false;
+                        if (coordinate + 1 < resolution && values[i + stride] > values[i]) is_max = false;
+                        stride *= resolution;
+                    }
+                    if (is_max) ++modes;
+                }
+                return modes;
+            })
+        }
+    }
+
+    /// Estimate how much the model has learned
+    ///
+    /// Returns the relative reduction of the posterior variance of the latent preference,
+    /// averaged over the unit cube, compared to the prior: `0` without data, approaching `1` as
+    /// the preference is pinned down everywhere. The average is approximated with a fixed set of
+    /// 256 Halton points, so it is reproducible but coarse in many dimensions, where regions
+    /// between those points are not represented. Both variances use the current
+    /// hyperparameters, so the value can drop when they are re-estimated. The
+    /// [regularization](SLSFramework::get_regularization) is not part of either variance, see
+    /// [predict_variance_components](SLSFramework::predict_variance_components).
+    pub fn information_gain(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                const double prior = self->getRegressor().a;
+                const double noise = self->getRegressor().b;
+                if (self->data.X.cols() == 0 || prior <= 0.0) return 0.0;
+
+                const size_t n = 256;
+                double variance = 0.0;
+                for (size_t i = 1; i <= n; ++i) {
+                    const double s = self->getRegressor().estimate_s(self->haltonPoint(i));
+                    variance += std::max(s * s - noise, 0.0);
+                }
+                return 1.0 - variance / n / prior;
+            })
+        }
+    }
+
+    /// Check whether the most recent step changed the model by more than `tol`
+    ///
+    /// Compares the posterior mean before and after the step at every point sampled so far,
+    /// including the ones the step added. Returns `true` if it moved by more than `tol` at any
+    /// of them, and always for the first step. A step that barely changed the model suggests the
+    /// options on its slider were too similar. `false` if there is no step to compare, i.e.
+    /// before the first one and after the data was edited, like for
+    /// [amend_last_comparison](SLSFramework::amend_last_comparison).
+    pub fn last_comparison_changed_model(&self, tol: f64) -> bool {
+        unsafe {
+            cpp!([self as "SLSFramework*", tol as "double"] -> bool as "bool" {
+                if (self->previous_slider == nullptr || self->previous_regressor == nullptr) return false;
+                if (self->previous_data.X.cols() == 0) return true;
+
+                for (Index i = 0; i < self->data.X.cols(); ++i) {
+                    const VectorXd x = self->data.X.col(i);
+                    const double before = self->previous_regressor->estimate_y(x);
+                    if (std::abs(self->getRegressor().estimate_y(x) - before) > tol) return true;
+                }
+                return false;
+            })
+        }
+    }
+
+    /// Check whether two frameworks have the same model, up to `tol`
+    ///
+    /// The posterior means of both are compared at every point sampled by either of them, the
+    /// places where the models are determined by data. Frameworks of different dimension are
+    /// never equal, two frameworks without data always are. Useful to check that a persistence
+    /// round trip or a merge of data kept the model.
+    pub fn model_approx_eq(&self, other: &SLSFramework, tol: f64) -> bool {
+        if self.dimension() != other.dimension() {
+            return false;
+        }
+        self.get_all_points()
+            .iter()
+            .chain(&other.get_all_points())
+            .all(|x| (self.predict(x).0 - other.predict(x).0).abs() <= tol)
+    }
+
+    /// Get a hash of the model, for caching predictions
+    ///
+    /// Hashes the [sampled points](SLSFramework::get_all_points), the
+    /// [comparisons](SLSFramework::get_comparisons) and the hyperparameters of the regression
+    /// (signal variance, length scale and noise) with 64-bit FNV-1a, over the exact bits of
+    /// every value. The same data and hyperparameters always give the same fingerprint, also
+    /// after a [snapshot](SLSFramework::snapshot) round trip or in another process, and every
+    /// step changes it. Different models can collide, and the hash is not cryptographically
+    /// secure, so it must not be trusted with data from untrusted sources.
+    pub fn model_fingerprint(&self) -> u64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> u64 as "uint64_t" {
+                uint64_t hash = 0xcbf29ce484222325;
+                const auto add = [&](uint64_t value) {
+                    for (int i = 0; i < 8; ++i)
+                    {
+                        hash ^= (value >> (8 * i)) & 0xff;
+                        hash *= 0x100000001b3;
+                    }
+                };
+                const auto add_double = [&](double value) {
+                    uint64_t bits;
+                    std::memcpy(&bits, &value, sizeof(bits));
+                    add(bits);
+                };
+
+                // An empty X can have zero rows or one per dimension, so hash the dimension instead
+                const MatrixXd& X = self->data.X;
+                add(self->dimension);
+                add(X.cols());
+                for (Index i = 0; i < X.size(); ++i) add_double(X.data()[i]);
+                add(self->data.D.size());
+                for (const auto& preference : self->data.D)
+                {
+                    add(preference.size());
+                    for (size_t k = 0; k < preference.size(); ++k) add(preference[k]);
+                }
+                add_double(self->getRegressor().a);
+                add_double(self->getRegressor().r);
+                add_double(self->getRegressor().b);
+                return hash;
+            })
+        }
+    }
+
+    /// Get the Cholesky factor of the kernel matrix of the sampled points
+    ///
+    /// Returns the lower triangular `L` with `L L^T = C` and its size `n`, the number of sampled
+    /// points. `C` is the kernel matrix of the regression including the
+    /// [regularization](SLSFramework::get_regularization) on its diagonal, for the current
+    /// hyperparameters. `L` is in column-major order, entry `(i, j)` is at index `j * n + i`.
+    /// It is computed on every call.
+    pub fn kernel_cholesky(&self) -> (Vec<f64>, usize) {
+        unsafe {
+            let factor = cpp!([self as "SLSFramework*"] -> *const u8 as "const MatrixXd *" {
+                return new MatrixXd(self->getRegressor().C.llt().matrixL());
+            });
+            let n = cpp!([factor as "const MatrixXd*"] -> usize as "size_t" {
+                return factor->rows();
+            });
+            let res = as_rust_matrix(factor).concat();
+            cpp!([factor as "const MatrixXd*"] {
+                delete factor;
+            });
+            (res, n)
+        }
+    }
+
+    /// Get the lowest and the highest latent score of the sampled points
+    ///
+    /// The posterior mean between the samples usually stays within this range, which makes it
+    /// a consistent scale for displaying the preference landscape. `(NaN, NaN)` if there is no
+    /// data yet.
+    pub fn score_range(&self) -> (f64, f64) {
+        let mut res = [0.0f64; 2];
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", ptr as "double*"] {
+                const VectorXd& y = self->getRegressor().y;
+                ptr[0] = y.size() == 0 ? NAN : y.minCoeff();
+                ptr[1] = y.size() == 0 ? NAN : y.maxCoeff();
+            });
+        }
+        (res[0], res[1])
+    }
+
+    /// Measure how consistent the recorded preferences are with the model
+    ///
+    /// Returns the fraction of comparisons whose preferred point has a higher latent score than
+    /// every point it was preferred over, under the fitted scores of the sampled points. `1`
+    /// means the model explains every answer; contradictory answers (A over B, B over C and C
+    /// over A) can't all be satisfied and lower it. `NaN` if there are no comparisons.
+    pub fn preference_consistency(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                const VectorXd& y = self->getRegressor().y;
+                const auto& D = self->data.D;
+                if (D.empty()) return NAN;
+
+                size_t satisfied = 0;
+                for (const Preference& p : D) {
+                    bool all = true;
+                    for (size_t k = 1; k < p.size(); ++k) all = all && y(p[0]) > y(p[k]);
+                    if (all) ++satisfied;
+                }
+                return double(satisfied) / D.size();
+            })
+        }
+    }
+
+    /// Compute the probability that the preference at `x` exceeds the current best
+    ///
+    /// This is `P(f(x) > y_max)` under the posterior at `x`, where `y_max` is the latent score of
+    /// the best point sampled so far. `NaN` if there is no data yet.
+    pub fn probability_of_improvement(&self, x: &[f64]) -> f64 {
+        let (mean, variance) = self.predict(x);
+        let y_max = unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->y_max;
+            })
+        };
+        if variance <= 0. {
+            return if mean > y_max { 1. } else if y_max.is_nan() { y_max } else { 0. };
+        }
+        normal_cdf((mean - y_max) / variance.sqrt())
+    }
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    unsafe {
+        cpp!([z as "double"] -> f64 as "double" {
+            return 0.5 * std::erfc(-z / std::sqrt(2.0));
+        })
+    }
+}
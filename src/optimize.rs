@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+use crate::{SLSFramework, Slider};
+
+/// Outcome of [SLSFramework::optimize]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionResult {
+    /// The best point at the end
+    pub x_max: Vec<f64>,
+    /// The latent score of `x_max` at the end
+    pub y_max: f64,
+    /// The best latent score after each step, as estimated in that step
+    ///
+    /// Every step re-estimates all scores, so this is not necessarily increasing.
+    pub best_scores: Vec<f64>,
+    /// Wall-clock time of the whole run, including the time spent in `choose`
+    pub elapsed: Duration,
+}
+
+impl SLSFramework {
+    /// Run `iterations` steps without user interaction
+    ///
+    /// `choose` is shown each slider and returns the chosen position on it, which is passed to
+    /// [proceed_optimization](SLSFramework::proceed_optimization). The best score is recorded
+    /// after every step, there is no way to turn that off. Useful for simulations and
+    /// benchmarks, see also [auto_step](SLSFramework::auto_step).
+    pub fn optimize<F>(&mut self, iterations: usize, mut choose: F) -> SessionResult
+    where
+        F: FnMut(&Slider) -> f64,
+    {
+        let start = Instant::now();
+        let mut best_scores = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let pos = choose(&self.current_slider());
+            self.proceed_optimization(pos);
+            best_scores.push(self.y_max());
+        }
+
+        SessionResult {
+            x_max: self.get_x_max(),
+            y_max: self.y_max(),
+            best_scores,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    fn y_max(&self) -> f64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->y_max;
+            })
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{SLSError, SLSFramework};
+
+/// A [SLSFramework] that may not be initialized yet
+///
+/// [SLSFramework] can't implement `Default`, since it needs to know the dimension. This wrapper
+/// defaults to no framework, so it can be part of `#[derive(Default)]` structs, and is
+/// [initialized](OptionalSLS::initialize) once the dimension is known. Accessing it before
+/// returns [SLSError::Uninitialized].
+#[derive(Clone, Default)]
+pub struct OptionalSLS(pub Option<SLSFramework>);
+
+impl OptionalSLS {
+    /// Initialize with a new framework for `dim` dimensions
+    ///
+    /// Replaces any previous framework. Fails like [SLSFramework::try_new].
+    pub fn initialize(&mut self, dim: usize) -> Result<&mut SLSFramework, SLSError> {
+        self.0 = Some(SLSFramework::try_new(dim)?);
+        self.get_mut()
+    }
+
+    /// Check whether there is a framework
+    pub fn is_initialized(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Get the framework
+    pub fn get(&self) -> Result<&SLSFramework, SLSError> {
+        self.0.as_ref().ok_or(SLSError::Uninitialized)
+    }
+
+    /// Get the framework mutably
+    pub fn get_mut(&mut self) -> Result<&mut SLSFramework, SLSError> {
+        self.0.as_mut().ok_or(SLSError::Uninitialized)
+    }
+}
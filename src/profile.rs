@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::SLSFramework;
+
+/// Preset configurations for [SLSFramework::new_with_profile]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Keeps every step responsive: a few acquisition restarts, cut off after 100ms
+    Interactive,
+    /// The best sliders at any cost: many acquisition restarts and MAP hyperparameters
+    Accurate,
+    /// As little computation as possible: a single acquisition run, fixed hyperparameters
+    Fast,
+}
+
+impl Profile {
+    fn apply(self, sls: &mut SLSFramework) {
+        match self {
+            Profile::Interactive => {
+                sls.set_acquisition_restarts(3);
+                sls.set_acquisition_timeout(Some(Duration::from_millis(100)));
+                sls.set_use_map_hyperparameters(false);
+            }
+            Profile::Accurate => {
+                sls.set_acquisition_restarts(10);
+                sls.set_acquisition_timeout(None);
+                sls.set_use_map_hyperparameters(true);
+            }
+            Profile::Fast => {
+                sls.set_acquisition_restarts(1);
+                sls.set_acquisition_timeout(None);
+                sls.set_use_map_hyperparameters(false);
+            }
+        }
+    }
+}
+
+impl SLSFramework {
+    /// Initializes the algorithm with a preset configuration.
+    ///
+    /// `dim` is the number of dimensions of the parameter space. The individual settings can
+    /// still be changed afterwards.
+    pub fn new_with_profile(dim: usize, profile: Profile) -> Self {
+        let mut sls = SLSFramework::new(dim);
+        profile.apply(&mut sls);
+        sls
+    }
+}
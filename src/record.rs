@@ -0,0 +1,71 @@
+use crate::{as_rust_vec, SLSFramework, Slider};
+
+/// What happened in one step, see [SLSFramework::last_step_record]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepRecord {
+    /// The slider that was shown
+    pub slider: Slider,
+    /// The position chosen on it
+    ///
+    /// `0`, `0.5` or `1` for [proceed_optimization_triple](SLSFramework::proceed_optimization_triple).
+    pub position: f64,
+    /// The point added as preferred
+    pub point: Vec<f64>,
+    /// The point the acquisition function chose for the slider
+    ///
+    /// As chosen, before [range decay](SLSFramework::set_range_decay),
+    /// [jitter](SLSFramework::set_endpoint_jitter) or nudging a zero-length slider moved the
+    /// slider's end. Random during warm-up, the second end for the initial slider, and empty
+    /// if the slider was set by hand, e.g. with
+    /// [set_manual_slider](SLSFramework::set_manual_slider).
+    pub acquisition_point: Vec<f64>,
+    /// The best point right after the step
+    ///
+    /// Stored at the time of the step, later changes of the data don't affect it.
+    pub x_max: Vec<f64>,
+    /// The latent score of `x_max` right after the step
+    pub y_max: f64,
+}
+
+impl SLSFramework {
+    /// Get a record of the most recent step
+    ///
+    /// Collects everything needed to log or replay the step in one value. `None` if no step has
+    /// been taken since the framework was created or [reset](SLSFramework::reset).
+    pub fn last_step_record(&self) -> Option<StepRecord> {
+        unsafe {
+            let slider = cpp!([self as "SLSFramework*"] -> *const u8 as "const Slider *" {
+                return self->step_slider.get();
+            });
+            if slider.is_null() {
+                return None;
+            }
+            let slider = self.read_slider(slider);
+
+            let position = cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->step_position;
+            });
+            let point = cpp!([self as "SLSFramework*"] -> *const u8 as "const VectorXd *" {
+                return &self->step_point;
+            });
+            let acquisition_point = cpp!([self as "SLSFramework*"] -> *const u8 as "const VectorXd *" {
+                return &self->step_acquisition_point;
+            });
+            let x_max = cpp!([self as "SLSFramework*"] -> *const u8 as "const VectorXd *" {
+                return &self->step_x_max;
+            });
+            let y_max = cpp!([self as "SLSFramework*"] -> f64 as "double" {
+                return self->step_y_max;
+            });
+
+            Some(StepRecord {
+                slider,
+                position,
+                point: as_rust_vec(point),
+                acquisition_point: as_rust_vec(acquisition_point),
+                x_max: as_rust_vec(x_max),
+                y_max,
+            })
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::SLSFramework;
+
+/// How the exploration parameter changes over a session, see
+/// [SLSFramework::set_acquisition_schedule]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// Always the same value
+    Constant(f64),
+    /// From `start` for the first slider chosen by the acquisition function to `end` `over` steps
+    /// later, constant afterwards
+    Linear { start: f64, end: f64, over: usize },
+}
+
+impl SLSFramework {
+    /// Change the exploration parameter over the session
+    ///
+    /// Before each slider is chosen, the
+    /// [exploration parameter](SLSFramework::set_acquisition_param) is set to the value of
+    /// `schedule` for the number of steps taken since the first one. A [Schedule::Linear] from
+    /// e.g. `0.05` to `0` explores early in the session and exploits late. A schedule replaces
+    /// the value set with `set_acquisition_param`, and calling `set_acquisition_param` afterwards
+    /// replaces the schedule by that constant value.
+    pub fn set_acquisition_schedule(&mut self, schedule: Schedule) {
+        let (start, end, over) = match schedule {
+            Schedule::Constant(v) => (v, v, 0),
+            Schedule::Linear { start, end, over } => (start, end, over),
+        };
+        unsafe {
+            cpp!([self as "SLSFramework*", start as "double", end as "double", over as "size_t"] {
+                self->has_schedule = true;
+                self->schedule_start = start;
+                self->schedule_end = end;
+                self->schedule_over = over;
+                self->acquisition_param = self->scheduledAcquisitionParam();
+            });
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{SLSError, SLSFramework, Slider};
+
+/// A comparison shown to the user
+///
+/// Obtained from [SLSFramework::begin_comparison] and handed back to [SLSFramework::submit]
+/// with the user's answer, which can happen much later, e.g. across `await` points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    slider: Slider,
+    generation: u64,
+}
+
+impl Comparison {
+    /// Get the slider to show
+    pub fn slider(&self) -> &Slider {
+        &self.slider
+    }
+
+    /// Get the parameters at position `0` of the slider
+    pub fn end_0(&self) -> &[f64] {
+        self.slider.end_0()
+    }
+
+    /// Get the parameters at position `1` of the slider
+    pub fn end_1(&self) -> &[f64] {
+        self.slider.end_1()
+    }
+}
+
+impl SLSFramework {
+    // Incremented whenever the slider changes
+    fn slider_generation(&self) -> u64 {
+        unsafe {
+            cpp!([self as "SLSFramework*"] -> u64 as "uint64_t" {
+                return self->slider_generation;
+            })
+        }
+    }
+
+    /// Start a comparison on the current slider
+    pub fn begin_comparison(&self) -> Comparison {
+        Comparison {
+            slider: self.current_slider(),
+            generation: self.slider_generation(),
+        }
+    }
+
+    /// Record the answer to a comparison
+    ///
+    /// `pos` (`0 <= pos <= 1`) is the position the user chose on the slider of `comparison`.
+    /// This is [try_proceed_optimization](SLSFramework::try_proceed_optimization), but fails
+    /// with [SLSError::StaleComparison] if the slider changed since the comparison began, even
+    /// if the new slider happens to look the same. This catches answers racing each other, e.g.
+    /// from two browser tabs.
+    pub fn submit(&mut self, comparison: Comparison, pos: f64) -> Result<Vec<f64>, SLSError> {
+        if comparison.generation != self.slider_generation() {
+            return Err(SLSError::StaleComparison);
+        }
+        self.try_proceed_optimization(pos)
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{as_rust_vec, SLSError, SLSFramework};
+
+/// Geometry of a slider
+///
+/// Returned by [SLSFramework::current_slider]. This is a copy, so it stays the same when the
+/// framework proceeds to the next slider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slider {
+    end_0: Vec<f64>,
+    end_1: Vec<f64>,
+    orig_0: Vec<f64>,
+    orig_1: Vec<f64>,
+    warp: f64,
+    overshoot: f64,
+    groups: Vec<Vec<usize>>,
+}
+
+/// How the first slider is chosen, see [SLSFramework::set_initial_strategy]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialStrategy {
+    /// Two independent random points (the default)
+    RandomPair,
+    /// A random point and its reflection through the center of the parameter space
+    ///
+    /// The two points are usually far apart, which makes the first comparison more informative.
+    AntipodalPair,
+    /// The two given points
+    FixedEndpoints(Vec<f64>, Vec<f64>),
+}
+
+/// Where generated sliders start, see [SLSFramework::set_anchor_mode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnchorMode {
+    /// The maximum of the posterior mean, the best point known to the model (the default)
+    GlobalBest,
+    /// The point chosen in the most recent step
+    LastChoice,
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(ai, bi)| ai*bi).sum()
+}
+
+fn sub(a: &[f64], b: &[f64]) -> Vec<f64> {
+    a.iter().zip(b).map(|(ai, bi)| ai-bi).collect()
+}
+
+impl Slider {
+    /// Get the parameters at position `0`
+    pub fn end_0(&self) -> &[f64] {
+        &self.end_0
+    }
+
+    /// Get the parameters at position `1`
+    pub fn end_1(&self) -> &[f64] {
+        &self.end_1
+    }
+
+    /// Get the parameters at a position along the slider
+    ///
+    /// `pos` (`0 <= pos <= 1`) is the position along the slider. This is what
+    /// [SLSFramework::get_parameters_from_slider] returned while this was the current slider.
+    pub fn at(&self, pos: f64) -> Vec<f64> {
+        let pos = pos.max(-self.overshoot).min(1. + self.overshoot);
+        let pos = if (0. ..=1.).contains(&pos) { self.warp_position(pos) } else { pos };
+        let mut x: Vec<f64> =
+            self.end_0.iter().zip(&self.end_1).map(|(a, b)| a * (1. - pos) + b * pos).collect();
+
+        // Same as snapCategories in C++
+        for group in &self.groups {
+            let best = group.iter().fold(group[0], |best, &d| if x[d] > x[best] { d } else { best });
+            for &d in group {
+                x[d] = if d == best { 1. } else { 0. };
+            }
+        }
+        x
+    }
+
+    /// Get the two points the slider was constructed from
+    ///
+    /// The ends extend beyond these points. The first one is the current best (see
+    /// [anchor modes](SLSFramework::set_anchor_mode)), unless the slider is random. Both are
+    /// recorded as rejected when a position is chosen.
+    pub fn origins(&self) -> (&[f64], &[f64]) {
+        (&self.orig_0, &self.orig_1)
+    }
+
+    /// Get the length of the slider in parameter space
+    pub fn length(&self) -> f64 {
+        let d = sub(&self.end_1, &self.end_0);
+        dot(&d, &d).sqrt()
+    }
+
+    /// Get the direction of the slider in parameter space
+    ///
+    /// This is the normalized vector from the position at `0` to the position at `1`, or the
+    /// zero vector if the slider has zero length.
+    pub fn direction(&self) -> Vec<f64> {
+        let length = self.length();
+        if length == 0. {
+            return vec![0.; self.end_0.len()];
+        }
+        sub(&self.end_1, &self.end_0).iter().map(|d| d / length).collect()
+    }
+
+    /// Get the position whose parameters are closest to `target`
+    ///
+    /// The position is in `[0, 1]`, picking it is what an ideal rater with its optimum at
+    /// `target` would do. `0` if the slider has zero length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` does not have the dimension of the slider.
+    pub fn closest_position(&self, target: &[f64]) -> f64 {
+        assert_eq!(target.len(), self.end_0.len(), "point has the wrong number of dimensions");
+        let d = sub(&self.end_1, &self.end_0);
+        let pl = dot(&d, &d);
+        if pl == 0. {
+            return 0.;
+        }
+        let proj = (dot(&d, &sub(target, &self.end_0)) / pl).max(0.).min(1.);
+        self.unwarp_position(proj)
+    }
+
+    // Position of orig_0 along the slider, which warping keeps in place
+    fn warp_center(&self) -> f64 {
+        let d = sub(&self.end_1, &self.end_0);
+        (dot(&d, &sub(&self.orig_0, &self.end_0)) / dot(&d, &d)).max(0.).min(1.)
+    }
+
+    // Same as warpPosition in C++
+    fn warp_position(&self, pos: f64) -> f64 {
+        if self.warp == 1. {
+            return pos;
+        }
+
+        let t = self.warp_center();
+        if pos >= t {
+            if t >= 1. {
+                pos
+            } else {
+                t + (1. - t) * ((pos - t) / (1. - t)).powf(self.warp)
+            }
+        } else {
+            t - t * ((t - pos) / t).powf(self.warp)
+        }
+    }
+
+    // Inverse of warp_position
+    fn unwarp_position(&self, pos: f64) -> f64 {
+        if self.warp == 1. {
+            return pos;
+        }
+
+        let t = self.warp_center();
+        if pos >= t {
+            if t >= 1. {
+                pos
+            } else {
+                t + (1. - t) * ((pos - t) / (1. - t)).powf(1. / self.warp)
+            }
+        } else {
+            t - t * ((t - pos) / t).powf(1. / self.warp)
+        }
+    }
+}
+
+impl SLSFramework {
+    /// Get the current slider
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider, see
+    /// [set_skip_initial_random](SLSFramework::set_skip_initial_random).
+    pub fn current_slider(&self) -> Slider {
+        if !self.has_slider() {
+            panic!("{}", SLSError::NoSlider);
+        }
+        unsafe {
+            let slider = cpp!([self as "SLSFramework*"] -> *const u8 as "const Slider *" {
+                return self->slider.get();
+            });
+            self.read_slider(slider)
+        }
+    }
+
+    // slider must point to a Slider of this framework
+    pub(crate) unsafe fn read_slider(&self, slider: *const u8) -> Slider {
+        let end_0 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+            return &slider->end_0;
+        });
+        let end_1 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+            return &slider->end_1;
+        });
+        let orig_0 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+            return &slider->orig_0;
+        });
+        let orig_1 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+            return &slider->orig_1;
+        });
+        let warp = cpp!([self as "SLSFramework*"] -> f64 as "double" {
+            return self->slider_warp;
+        });
+        let overshoot = cpp!([self as "SLSFramework*"] -> f64 as "double" {
+            return self->slider_overshoot;
+        });
+        Slider {
+            end_0: as_rust_vec(end_0),
+            end_1: as_rust_vec(end_1),
+            orig_0: as_rust_vec(orig_0),
+            orig_1: as_rust_vec(orig_1),
+            warp,
+            overshoot,
+            groups: self.categorical_groups(),
+        }
+    }
+
+    fn categorical_groups(&self) -> Vec<Vec<usize>> {
+        unsafe {
+            let n = cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->categorical_groups.size();
+            });
+            (0..n)
+                .map(|i| {
+                    let len = cpp!([self as "SLSFramework*", i as "size_t"] -> usize as "size_t" {
+                        return self->categorical_groups[i].size();
+                    });
+                    let mut group = vec![0usize; len];
+                    let ptr = group.as_mut_ptr();
+                    cpp!([self as "SLSFramework*", i as "size_t", len as "size_t", ptr as "size_t*"] {
+                        for (size_t k = 0; k < len; ++k) {
+                            ptr[k] = self->categorical_groups[i][k];
+                        }
+                    });
+                    group
+                })
+                .collect()
+        }
+    }
+
+    /// Treat the dimensions at `indices` as one-hot encoding of a category
+    ///
+    /// Positions on sliders, as returned by
+    /// [get_parameters_from_slider](SLSFramework::get_parameters_from_slider),
+    /// [Slider::at] and recorded by [proceed_optimization](SLSFramework::proceed_optimization),
+    /// then have the largest of these coordinates set to `1` and the others to `0`, the first
+    /// one winning ties. The slider origins that a chosen point is recorded as preferred over are
+    /// snapped the same way, so all recorded points are valid one-hot vectors and the user never
+    /// sees an in-between category. The regression still treats the dimensions as continuous,
+    /// there is no categorical kernel, and slider ends are not snapped. Fails if there are fewer
+    /// than two indices, an index is out of range or already part of a group.
+    pub fn set_categorical_group(&mut self, indices: &[usize]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        let used: Vec<usize> = self.categorical_groups().concat();
+        let mut seen = Vec::new();
+        for &d in indices {
+            if d >= dim || used.contains(&d) || seen.contains(&d) {
+                return Err(SLSError::InvalidGroup);
+            }
+            seen.push(d);
+        }
+        if indices.len() < 2 {
+            return Err(SLSError::InvalidGroup);
+        }
+
+        let len = indices.len();
+        let indices = indices.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", len as "size_t", indices as "const size_t*"] {
+                self->categorical_groups.emplace_back(indices, indices + len);
+            });
+        }
+        Ok(())
+    }
+
+    /// Set how the first slider is chosen
+    ///
+    /// If no step has been taken yet, the current slider is replaced right away. Later random
+    /// sliders during [warm-up](SLSFramework::set_warmup_steps) always use random pairs. Fails if
+    /// fixed endpoints have the wrong dimension.
+    pub fn set_initial_strategy(&mut self, strategy: InitialStrategy) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        let code: i32 = match &strategy {
+            InitialStrategy::RandomPair => 0,
+            InitialStrategy::AntipodalPair => 1,
+            InitialStrategy::FixedEndpoints(a, b) => {
+                self.check_dimension(a)?;
+                self.check_dimension(b)?;
+                let a = a.as_ptr();
+                let b = b.as_ptr();
+                unsafe {
+                    cpp!([self as "SLSFramework*", a as "const double*", b as "const double*", dim as "size_t"] {
+                        self->initial_end_0 = Map<const VectorXd>(a, dim);
+                        self->initial_end_1 = Map<const VectorXd>(b, dim);
+                    });
+                }
+                2
+            }
+        };
+        unsafe {
+            cpp!([self as "SLSFramework*", code as "int"] {
+                self->initial_strategy = code;
+                if (self->x_max.rows() == 0)
+                {
+                    self->setInitialSlider();
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Set where generated sliders start
+    ///
+    /// With [AnchorMode::GlobalBest] every slider leads from the best point according to the
+    /// model to the point chosen by the acquisition function, so each comparison is against the
+    /// current optimum and the search converges there directly. With [AnchorMode::LastChoice]
+    /// sliders start from the point the user just chose instead, so the session follows the
+    /// user step by step. This feels more continuous, but a poor choice is not corrected by the
+    /// model before the next slider, and convergence is usually slower. Random sliders during
+    /// [warm-up](SLSFramework::set_warmup_steps) are not affected.
+    pub fn set_anchor_mode(&mut self, mode: AnchorMode) {
+        let code: i32 = match mode {
+            AnchorMode::GlobalBest => 0,
+            AnchorMode::LastChoice => 1,
+        };
+        unsafe {
+            cpp!([self as "SLSFramework*", code as "int"] {
+                self->anchor_mode = code;
+            });
+        }
+    }
+
+    /// Perturb the points generated sliders are built from.
+    ///
+    /// Both points chosen for a new slider get independent Gaussian noise with standard deviation
+    /// `sigma` (`>= 0`) in every coordinate, clamped to the unit cube. The acquisition function
+    /// tends to propose nearly the same slider over and over late in a session, jitter makes
+    /// the comparisons more varied at the cost of sliders that are no longer exactly the most
+    /// informative ones. Small values like `0.02` keep them close. The default is `0`, random
+    /// sliders are never perturbed.
+    pub fn set_endpoint_jitter(&mut self, sigma: f64) {
+        assert!(sigma >= 0., "endpoint jitter must not be negative");
+        unsafe {
+            cpp!([self as "SLSFramework*", sigma as "double"] {
+                self->endpoint_jitter = sigma;
+            });
+        }
+    }
+
+    /// Compute the slider the algorithm would generate next for the current data
+    ///
+    /// Returns the ends of the slider a step would be followed by if it added no data, without
+    /// changing the framework, e.g. to prefetch what the user will likely see next. The actual
+    /// next slider also depends on the answer to the current one, on any change made meanwhile
+    /// and on randomness (warm-up, restarts, jitter), so it can be quite different. This costs
+    /// as much as choosing a slider in a step. `None` before the first step.
+    pub fn peek_next_slider(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        unsafe {
+            let slider = cpp!([self as "SLSFramework*"] -> *const u8 as "const Slider *" {
+                if (self->x_max.rows() == 0) return nullptr;
+
+                SLSFramework copy(*self);
+                copy.updateSliderEnds();
+                return new Slider(*copy.slider);
+            });
+            if slider.is_null() {
+                return None;
+            }
+            let end_0 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+                return &slider->end_0;
+            });
+            let end_1 = cpp!([slider as "const Slider*"] -> *const u8 as "const VectorXd *" {
+                return &slider->end_1;
+            });
+            let ends = (as_rust_vec(end_0), as_rust_vec(end_1));
+            cpp!([slider as "const Slider*"] {
+                delete slider;
+            });
+            Some(ends)
+        }
+    }
+
+    /// Choose the far end of the next generated slider
+    ///
+    /// The next slider the algorithm generates leads from its
+    /// [anchor](SLSFramework::set_anchor_mode) to `x`, instead of to the maximum of the acquisition
+    /// function, even during [warm-up](SLSFramework::set_warmup_steps). Range decay and jitter
+    /// still apply. The candidate is used once: the slider after that is chosen by the acquisition
+    /// function again. The current slider is not changed, so after
+    /// [set_manual_slider](SLSFramework::set_manual_slider) the candidate applies to the slider
+    /// following the manual one. Before the first step there is no best point yet, so the candidate
+    /// waits for the slider after the initial one. Fails if `x` has the wrong dimension.
+    pub fn set_next_candidate(&mut self, x: &[f64]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        self.check_dimension(x)?;
+        let x = x.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t"] {
+                self->next_candidate = Map<const VectorXd>(x, dim);
+            });
+        }
+        Ok(())
+    }
+
+    /// Start every generated slider at `x`
+    ///
+    /// Instead of at its [anchor](SLSFramework::set_anchor_mode), each slider the algorithm
+    /// generates then starts exactly at `x` (position `0`) and ends at the point chosen by the
+    /// acquisition function, or at a random point during [warm-up](SLSFramework::set_warmup_steps).
+    /// Every comparison is then made against the same baseline, which some raters find easier,
+    /// but the sliders no longer pass through the best point found so far. `x` is never
+    /// perturbed by [jitter](SLSFramework::set_endpoint_jitter); if the other end reaches it,
+    /// only the other end is nudged away, see
+    /// [HealthWarning::ZeroLengthSlider](crate::HealthWarning::ZeroLengthSlider). The current
+    /// slider is not changed. Fails if `x` has the wrong dimension.
+    pub fn set_fixed_anchor(&mut self, x: &[f64]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        self.check_dimension(x)?;
+        let x = x.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", x as "const double*", dim as "size_t"] {
+                self->fixed_anchor = Map<const VectorXd>(x, dim);
+            });
+        }
+        Ok(())
+    }
+
+    /// Go back to starting sliders at their anchor after
+    /// [set_fixed_anchor](SLSFramework::set_fixed_anchor).
+    ///
+    /// Takes effect with the next generated slider.
+    pub fn clear_fixed_anchor(&mut self) {
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->fixed_anchor = VectorXd::Zero(0);
+            });
+        }
+    }
+
+    /// Replace the current slider with one from `a` to `b`
+    ///
+    /// The slider runs exactly from `a` (at `0`) to `b` (at `1`), without the enlargement
+    /// applied to generated sliders. The next
+    /// [proceed_optimization](SLSFramework::proceed_optimization) records the chosen point as
+    /// preferred over `a` and `b`, after which sliders are chosen by the algorithm again. Fails
+    /// if `a` or `b` has the wrong dimension, or if they are the same point.
+    pub fn set_manual_slider(&mut self, a: &[f64], b: &[f64]) -> Result<(), SLSError> {
+        let dim = self.dimension();
+        self.check_dimension(a)?;
+        self.check_dimension(b)?;
+        if a == b {
+            return Err(SLSError::IdenticalEndpoints);
+        }
+        let a = a.as_ptr();
+        let b = b.as_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", a as "const double*", b as "const double*", dim as "size_t"] {
+                self->replaceSlider(std::make_shared<Slider>(Map<const VectorXd>(a, dim), Map<const VectorXd>(b, dim), false));
+            });
+        }
+        Ok(())
+    }
+
+    /// Replace the current slider with one between the sampled points `i` and `j`
+    ///
+    /// Like [set_manual_slider](SLSFramework::set_manual_slider) with the points at `i` and `j`
+    /// of [get_all_points](SLSFramework::get_all_points), to let the user refine between two
+    /// earlier options. The next [proceed_optimization](SLSFramework::proceed_optimization)
+    /// records its point as preferred over both. Fails if an index is out of range, or if both
+    /// refer to the same point.
+    pub fn slider_between_samples(&mut self, i: usize, j: usize) -> Result<(), SLSError> {
+        let len = self.num_points();
+        for &index in &[i, j] {
+            if index >= len {
+                return Err(SLSError::IndexOutOfRange { index, len });
+            }
+        }
+        let identical = unsafe {
+            cpp!([self as "SLSFramework*", i as "size_t", j as "size_t"] -> bool as "bool" {
+                return self->data.X.col(i) == self->data.X.col(j);
+            })
+        };
+        if identical {
+            return Err(SLSError::IdenticalEndpoints);
+        }
+        unsafe {
+            cpp!([self as "SLSFramework*", i as "size_t", j as "size_t"] {
+                self->replaceSlider(std::make_shared<Slider>(self->data.X.col(i), self->data.X.col(j), false));
+            });
+        }
+        Ok(())
+    }
+
+    /// Show the slider of an earlier step again
+    ///
+    /// `step_index` counts the steps since the framework was created or
+    /// [reset](SLSFramework::reset), starting at `0`. The slider becomes the current one with
+    /// exactly the geometry it had, and the data of that step is kept, so the next
+    /// [proceed_optimization](SLSFramework::proceed_optimization) records a second, independent
+    /// answer to the same comparison. Comparing both answers measures how reliable the user is.
+    /// Fails if fewer steps were taken.
+    pub fn repeat_comparison(&mut self, step_index: usize) -> Result<(), SLSError> {
+        let len = unsafe {
+            cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->slider_history.size();
+            })
+        };
+        if step_index >= len {
+            return Err(SLSError::StepOutOfRange { index: step_index, len });
+        }
+        unsafe {
+            cpp!([self as "SLSFramework*", step_index as "size_t"] {
+                self->replaceSlider(self->slider_history[step_index]);
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the length of the current slider in parameter space
+    pub fn slider_length(&self) -> f64 {
+        self.current_slider().length()
+    }
+
+    /// Get the direction of the current slider in parameter space
+    ///
+    /// See [Slider::direction].
+    pub fn slider_direction(&self) -> Vec<f64> {
+        self.current_slider().direction()
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{SLSError, SLSFramework};
+
+/// The state of a [SLSFramework] as plain data
+///
+/// Obtained from [SLSFramework::snapshot] and turned back into a framework with
+/// [SLSFramework::from_snapshot]. How it is stored is up to the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSnapshot {
+    /// Number of dimensions of the parameter space
+    pub dimension: usize,
+    /// All points sampled so far
+    pub points: Vec<Vec<f64>>,
+    /// Preferences as `(preferred, rejected)`, indexing into `points`
+    pub comparisons: Vec<(usize, Vec<usize>)>,
+    /// The two points the current slider was constructed from
+    pub slider_origins: (Vec<f64>, Vec<f64>),
+    /// The parameters at both ends of the current slider
+    pub slider_ends: (Vec<f64>, Vec<f64>),
+    /// Number of steps taken, which warm-up, range decay and schedules depend on
+    pub iterations: usize,
+}
+
+impl StateSnapshot {
+    fn check_dimension(&self, v: &[f64]) -> Result<(), SLSError> {
+        if v.len() != self.dimension {
+            return Err(SLSError::DimensionMismatch {
+                expected: self.dimension,
+                found: v.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), SLSError> {
+        validate_dataset(self.dimension, &self.points, &self.comparisons)?;
+        self.check_dimension(&self.slider_origins.0)?;
+        self.check_dimension(&self.slider_origins.1)?;
+        self.check_dimension(&self.slider_ends.0)?;
+        self.check_dimension(&self.slider_ends.1)?;
+        Ok(())
+    }
+}
+
+fn validate_dataset(
+    dimension: usize,
+    points: &[Vec<f64>],
+    comparisons: &[(usize, Vec<usize>)],
+) -> Result<(), SLSError> {
+    for p in points {
+        if p.len() != dimension {
+            return Err(SLSError::DimensionMismatch {
+                expected: dimension,
+                found: p.len(),
+            });
+        }
+    }
+
+    let len = points.len();
+    for (i, (preferred, rejected)) in comparisons.iter().enumerate() {
+        for &index in Some(preferred).into_iter().chain(rejected) {
+            if index >= len {
+                return Err(SLSError::IndexOutOfRange { index, len });
+            }
+        }
+        if rejected.is_empty() || rejected.contains(preferred) {
+            return Err(SLSError::InvalidComparison { index: i });
+        }
+    }
+    Ok(())
+}
+
+impl SLSFramework {
+    /// Capture the current state of the algorithm
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current slider, see
+    /// [set_skip_initial_random](SLSFramework::set_skip_initial_random).
+    pub fn snapshot(&self) -> StateSnapshot {
+        let slider = self.current_slider();
+        let (orig_0, orig_1) = slider.origins();
+        let iterations = unsafe {
+            cpp!([self as "SLSFramework*"] -> usize as "size_t" {
+                return self->num_iterations;
+            })
+        };
+
+        StateSnapshot {
+            dimension: self.dimension(),
+            points: self.get_all_points(),
+            comparisons: self.get_comparisons(),
+            slider_origins: (orig_0.to_vec(), orig_1.to_vec()),
+            slider_ends: (slider.end_0().to_vec(), slider.end_1().to_vec()),
+            iterations,
+        }
+    }
+
+    /// Rebuild a framework from a [StateSnapshot]
+    ///
+    /// The regression is recomputed from the stored data and the slider is restored with exactly
+    /// its stored ends and origins. If both ends coincide, the second one is nudged away like
+    /// for generated sliders, and the number of steps taken is restored. Fails if the dimension
+    /// is zero, a point has the wrong dimension, or a comparison refers to a point that does not
+    /// exist, rejects no point or rejects its preferred point.
+    pub fn from_snapshot(snap: &StateSnapshot) -> Result<SLSFramework, SLSError> {
+        snap.validate()?;
+
+        let mut sls = SLSFramework::try_new(snap.dimension)?;
+        sls.load_dataset(&snap.points, &snap.comparisons);
+
+        let fw = &mut sls;
+        let dim = snap.dimension;
+        let end_0 = snap.slider_ends.0.as_ptr();
+        let end_1 = snap.slider_ends.1.as_ptr();
+        let orig_0 = snap.slider_origins.0.as_ptr();
+        let orig_1 = snap.slider_origins.1.as_ptr();
+        unsafe {
+            cpp!([fw as "SLSFramework*", dim as "size_t", end_0 as "const double*", end_1 as "const double*"] {
+                fw->setSlider(Map<const VectorXd>(end_0, dim), Map<const VectorXd>(end_1, dim), false);
+            });
+            cpp!([fw as "SLSFramework*", dim as "size_t", orig_0 as "const double*", orig_1 as "const double*"] {
+                fw->slider->orig_0 = Map<const VectorXd>(orig_0, dim);
+                fw->slider->orig_1 = Map<const VectorXd>(orig_1, dim);
+            });
+            let iterations = snap.iterations;
+            cpp!([fw as "SLSFramework*", iterations as "size_t"] {
+                fw->num_iterations = iterations;
+            });
+        }
+        Ok(sls)
+    }
+
+    /// Replace all data at once
+    ///
+    /// `points` become the sampled points and `comparisons` the preferences between them, as
+    /// `(preferred, rejected)` indexing into `points`. The regression is computed once for the
+    /// whole dataset and a new slider is constructed from it. This is much faster than adding a
+    /// large dataset one comparison at a time.
+    ///
+    /// Like after [reset](SLSFramework::reset), the steps taken so far are forgotten: the
+    /// history of sliders and best points, absolute observations and the iteration count that
+    /// warm-up, range decay and schedules depend on. Settings are kept.
+    ///
+    /// Fails without changing anything if a point has the wrong dimension, a comparison refers
+    /// to a point that does not exist, rejects no point or rejects its preferred point.
+    pub fn set_dataset(
+        &mut self,
+        points: &[Vec<f64>],
+        comparisons: &[(usize, Vec<usize>)],
+    ) -> Result<(), SLSError> {
+        validate_dataset(self.dimension(), points, comparisons)?;
+        self.load_dataset(points, comparisons);
+        unsafe {
+            cpp!([self as "SLSFramework*"] {
+                self->updateSliderEnds();
+            });
+        }
+        Ok(())
+    }
+
+    // Expects validated data, forgets the steps taken but leaves the slider untouched
+    fn load_dataset(&mut self, points: &[Vec<f64>], comparisons: &[(usize, Vec<usize>)]) {
+        let fw = self;
+        let dim = fw.dimension();
+        let n = points.len();
+        let points: Vec<f64> = points.iter().flatten().cloned().collect();
+        let points = points.as_ptr();
+
+        unsafe {
+            cpp!([fw as "SLSFramework*", dim as "size_t", n as "size_t", points as "const double*"] {
+                fw->clearSession();
+                fw->data.X = Map<const MatrixXd>(points, dim, n);
+                fw->data.D.clear();
+            });
+            for (preferred, rejected) in comparisons {
+                let preferred = *preferred;
+                let len = rejected.len();
+                let rejected = rejected.as_ptr();
+                cpp!([fw as "SLSFramework*", preferred as "size_t", len as "size_t", rejected as "const size_t*"] {
+                    Preference p;
+                    p.push_back(preferred);
+                    for (size_t k = 0; k < len; ++k) {
+                        p.push_back(rejected[k]);
+                    }
+                    fw->data.D.push_back(p);
+                });
+            }
+            cpp!([fw as "SLSFramework*"] {
+                fw->computeRegression();
+                if (fw->data.X.cols() > 0) fw->updateBest();
+            });
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Helpers for testing code built on this crate
+//!
+//! Only available with the `testing` feature. Meant for crates that persist frameworks, e.g. via
+//! [snapshot](SLSFramework::snapshot) and [from_snapshot](SLSFramework::from_snapshot), to check
+//! that a restored framework continues exactly like the original.
+
+use crate::SLSFramework;
+
+/// Positions chosen on the sliders of the steps taken by [assert_frameworks_equivalent]
+const POSITIONS: [f64; 4] = [0.25, 0.75, 0.5, 1.0];
+
+fn assert_close(what: &str, step: usize, a: &[f64], b: &[f64]) {
+    let close = a.len() == b.len()
+        && a.iter().zip(b).all(|(x, y)| (x - y).abs() <= 1e-9 * (1.0 + x.abs().max(y.abs())));
+    assert!(close, "{} differs after {} steps: {:?} != {:?}", what, step, a, b);
+}
+
+fn assert_step_equivalent(step: usize, a: &SLSFramework, b: &SLSFramework) {
+    let (slider_a, slider_b) = (a.current_slider(), b.current_slider());
+    assert_close("slider end 0", step, slider_a.end_0(), slider_b.end_0());
+    assert_close("slider end 1", step, slider_a.end_1(), slider_b.end_1());
+
+    let (x_max_a, x_max_b) = (a.get_x_max(), b.get_x_max());
+    assert_close("x_max", step, &x_max_a, &x_max_b);
+    if !x_max_a.is_empty() {
+        assert_close("y_max", step, &[a.predict(&x_max_a).0], &[b.predict(&x_max_b).0]);
+    }
+}
+
+// Seeds a copy so that the nudge of coinciding slider ends and endpoint jitter repeat
+fn seed_copy(sls: &mut SLSFramework) {
+    unsafe {
+        cpp!([sls as "SLSFramework*"] {
+            sls->seed(0);
+        });
+    }
+}
+
+/// Assert that two frameworks behave the same
+///
+/// Both must hold the same data. Copies of them are then advanced through the same short
+/// sequence of steps, and after every step their sliders, best points and the scores of those must
+/// agree. The copies use [deterministic acquisition](SLSFramework::set_deterministic_acquisition),
+/// no warm-up and random generators with the same seed, so that both draw the same random
+/// numbers; `a` and `b` are not modified.
+///
+/// Equivalence is checked through this behavior, not by comparing the internal C++ state, so
+/// settings that the steps don't depend on are not compared.
+///
+/// # Panics
+///
+/// Panics with a description of the first difference found.
+pub fn assert_frameworks_equivalent(a: &SLSFramework, b: &SLSFramework) {
+    assert_eq!(a.get_all_points(), b.get_all_points(), "points differ");
+    assert_eq!(a.get_comparisons(), b.get_comparisons(), "comparisons differ");
+    assert_step_equivalent(0, a, b);
+
+    let (mut a, mut b) = (a.clone(), b.clone());
+    a.set_deterministic_acquisition(true);
+    b.set_deterministic_acquisition(true);
+    a.set_warmup_steps(0);
+    b.set_warmup_steps(0);
+    seed_copy(&mut a);
+    seed_copy(&mut b);
+    for (i, &pos) in POSITIONS.iter().enumerate() {
+        a.proceed_optimization(pos);
+        b.proceed_optimization(pos);
+        assert_step_equivalent(i + 1, &a, &b);
+    }
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use crate::SLSFramework;
+
+/// Wall-clock time spent in the parts of a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StepTimings {
+    /// Fitting the regression to all data, which grows cubically with the number of points
+    pub regression: Duration,
+    /// Choosing the next slider, mostly maximizing the regression and the acquisition function
+    pub acquisition: Duration,
+}
+
+impl SLSFramework {
+    /// Get the time spent in the most recent step
+    ///
+    /// All zero before the first [proceed_optimization](SLSFramework::proceed_optimization).
+    pub fn last_step_timings(&self) -> StepTimings {
+        let mut ns = [0i64; 2];
+        let ptr = ns.as_mut_ptr();
+        unsafe {
+            cpp!([self as "SLSFramework*", ptr as "int64_t*"] {
+                ptr[0] = self->regression_ns;
+                ptr[1] = self->acquisition_ns;
+            });
+        }
+        StepTimings {
+            regression: Duration::from_nanos(ns[0] as u64),
+            acquisition: Duration::from_nanos(ns[1] as u64),
+        }
+    }
+}